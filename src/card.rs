@@ -1,10 +1,14 @@
-use std::fmt::{Debug, Display};
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
 
 use colored::{ColoredString, Colorize};
 
-use crate::player::Property;
+use crate::{player::Property, state::Rules};
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Card {
     pub number: Number,
     pub color: Color,
@@ -22,48 +26,41 @@ impl Display for Card {
 }
 
 impl Card {
-    pub(crate) fn satisfies(&self, hinted_property: Property) -> bool {
+    /// Whether a hint of `hinted_property` touches this card, see [`Rules::touches`].
+    pub(crate) fn satisfies(&self, hinted_property: Property, rules: &Rules) -> bool {
         match hinted_property {
-            Property::Color(c) => self.color == c,
+            Property::Color(color) => rules.touches(color, self.color),
             Property::Number(n) => self.number == n,
         }
     }
-
-    pub(crate) fn next(color: Color, number: Option<Number>) -> Option<Self> {
-        match number {
-            Some(Number::One) => Some(Card {
-                number: Number::Two,
-                color,
-            }),
-            Some(Number::Two) => Some(Card {
-                number: Number::Three,
-                color,
-            }),
-            Some(Number::Three) => Some(Card {
-                number: Number::Four,
-                color,
-            }),
-            Some(Number::Four) => Some(Card {
-                number: Number::Five,
-                color,
-            }),
-            Some(Number::Five) => None,
-            None => Some(Card {
-                number: Number::One,
-                color,
-            }),
-        }
-    }
 }
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Number {
     One,
     Two,
     Three,
     Four,
     Five,
+    /// Starts a pile of an up-or-down suit, which then continues with a two or a four.
+    Start,
 }
 impl Number {
+    pub const ALL: [Number; 6] = [
+        Number::One,
+        Number::Two,
+        Number::Three,
+        Number::Four,
+        Number::Five,
+        Number::Start,
+    ];
+
+    /// The position in [`Number::ALL`].
+    pub(crate) fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// The rank of the number. The start card has none and counts as 0.
     pub(crate) fn score(&self) -> usize {
         match self {
             Number::One => 1,
@@ -71,23 +68,23 @@ impl Number {
             Number::Three => 3,
             Number::Four => 4,
             Number::Five => 5,
+            Number::Start => 0,
         }
     }
 
-    pub(crate) fn comes_after(&self, current: Option<Number>) -> bool {
-        current
-            == match self {
-                Number::One => None,
-                Number::Two => Some(Number::One),
-                Number::Three => Some(Number::Two),
-                Number::Four => Some(Number::Three),
-                Number::Five => Some(Number::Four),
-            }
+    pub(crate) fn increase(&self) -> Option<Self> {
+        match self {
+            Number::One => Some(Number::Two),
+            Number::Two => Some(Number::Three),
+            Number::Three => Some(Number::Four),
+            Number::Four => Some(Number::Five),
+            Number::Five | Number::Start => None,
+        }
     }
 
     pub(crate) fn decrease(&self) -> Option<Self> {
         match self {
-            Number::One => None,
+            Number::One | Number::Start => None,
             Number::Two => Some(Number::One),
             Number::Three => Some(Number::Two),
             Number::Four => Some(Number::Three),
@@ -97,15 +94,46 @@ impl Number {
 }
 
 #[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Color {
     White,
     Green,
     Yellow,
     Red,
     Blue,
+    /// Touched by every color hint, but cannot be hinted itself.
+    Rainbow,
+    Teal,
+    /// The dark suit, with a single copy of every card.
+    Black,
+    /// Touched by no color hint.
+    Gray,
 }
 
 impl Color {
+    /// Every color, in declaration order.
+    pub const ALL: [Color; 9] = [
+        Color::White,
+        Color::Green,
+        Color::Yellow,
+        Color::Red,
+        Color::Blue,
+        Color::Rainbow,
+        Color::Teal,
+        Color::Black,
+        Color::Gray,
+    ];
+
+    /// The position in [`Color::ALL`].
+    pub(crate) fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Whether hints of this color can be given.
+    pub fn is_clue_color(&self) -> bool {
+        !matches!(self, Color::Rainbow | Color::Gray)
+    }
+
     fn tint_str(&self, str: &str) -> ColoredString {
         match self {
             Color::White => str.white(),
@@ -113,6 +141,10 @@ impl Color {
             Color::Yellow => str.yellow(),
             Color::Red => str.red(),
             Color::Blue => str.blue(),
+            Color::Rainbow => str.magenta(),
+            Color::Teal => str.cyan(),
+            Color::Black => str.bright_black(),
+            Color::Gray => str.dimmed(),
         }
     }
 }
@@ -125,6 +157,10 @@ impl Display for Color {
             Color::Yellow => 'y',
             Color::Red => 'r',
             Color::Blue => 'b',
+            Color::Rainbow => 'm',
+            Color::Teal => 't',
+            Color::Black => 'k',
+            Color::Gray => 'e',
         };
 
         write!(f, "{char}")
@@ -139,10 +175,156 @@ impl Display for Number {
             Number::Three => '3',
             Number::Four => '4',
             Number::Five => '5',
+            Number::Start => 's',
         };
 
         write!(f, "{char}")
     }
 }
 
+impl Color {
+    pub(crate) fn from_char(char: char) -> Option<Self> {
+        match char {
+            'w' => Some(Color::White),
+            'g' => Some(Color::Green),
+            'y' => Some(Color::Yellow),
+            'r' => Some(Color::Red),
+            'b' => Some(Color::Blue),
+            'm' => Some(Color::Rainbow),
+            't' => Some(Color::Teal),
+            'k' => Some(Color::Black),
+            'e' => Some(Color::Gray),
+            _ => None,
+        }
+    }
+}
+
+impl Number {
+    pub(crate) fn from_char(char: char) -> Option<Self> {
+        match char {
+            '1' => Some(Number::One),
+            '2' => Some(Number::Two),
+            '3' => Some(Number::Three),
+            '4' => Some(Number::Four),
+            '5' => Some(Number::Five),
+            's' => Some(Number::Start),
+            _ => None,
+        }
+    }
+}
+
+/// Error when parsing cards from their short notation like `r3`.
+/// Offsets count characters from the start of the parsed string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseCardError {
+    UnknownColor { offset: usize, found: char },
+    UnknownNumber { offset: usize, found: char },
+    UnexpectedEnd { offset: usize },
+    TrailingCharacter { offset: usize, found: char },
+}
+
+impl ParseCardError {
+    pub fn offset(&self) -> usize {
+        match *self {
+            ParseCardError::UnknownColor { offset, .. }
+            | ParseCardError::UnknownNumber { offset, .. }
+            | ParseCardError::UnexpectedEnd { offset }
+            | ParseCardError::TrailingCharacter { offset, .. } => offset,
+        }
+    }
+}
+
+impl Display for ParseCardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseCardError::UnknownColor { offset, found } => {
+                write!(f, "unknown color '{found}' at offset {offset}")
+            }
+            ParseCardError::UnknownNumber { offset, found } => {
+                write!(f, "unknown number '{found}' at offset {offset}")
+            }
+            ParseCardError::UnexpectedEnd { offset } => {
+                write!(f, "unexpected end at offset {offset}")
+            }
+            ParseCardError::TrailingCharacter { offset, found } => {
+                write!(f, "unexpected '{found}' at offset {offset}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+fn parse_color(
+    chars: &mut impl Iterator<Item = char>,
+    offset: usize,
+) -> Result<Color, ParseCardError> {
+    let found = chars
+        .next()
+        .ok_or(ParseCardError::UnexpectedEnd { offset })?;
+    Color::from_char(found).ok_or(ParseCardError::UnknownColor { offset, found })
+}
+
+fn parse_number(
+    chars: &mut impl Iterator<Item = char>,
+    offset: usize,
+) -> Result<Number, ParseCardError> {
+    let found = chars
+        .next()
+        .ok_or(ParseCardError::UnexpectedEnd { offset })?;
+    Number::from_char(found).ok_or(ParseCardError::UnknownNumber { offset, found })
+}
+
+fn expect_end(chars: &mut impl Iterator<Item = char>, offset: usize) -> Result<(), ParseCardError> {
+    match chars.next() {
+        Some(found) => Err(ParseCardError::TrailingCharacter { offset, found }),
+        None => Ok(()),
+    }
+}
+
+impl Card {
+    /// Parses the next card from `chars`, with `offset` being the position of its first character.
+    pub(crate) fn parse_next(
+        chars: &mut impl Iterator<Item = char>,
+        offset: usize,
+    ) -> Result<Self, ParseCardError> {
+        let color = parse_color(chars, offset)?;
+        let number = parse_number(chars, offset + 1)?;
+        Ok(Card { number, color })
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let color = parse_color(&mut chars, 0)?;
+        expect_end(&mut chars, 1)?;
+        Ok(color)
+    }
+}
+
+impl FromStr for Number {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let number = parse_number(&mut chars, 0)?;
+        expect_end(&mut chars, 1)?;
+        Ok(number)
+    }
+}
+
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let card = Card::parse_next(&mut chars, 0)?;
+        expect_end(&mut chars, 2)?;
+        Ok(card)
+    }
+}
+
 pub mod card_set;
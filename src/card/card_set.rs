@@ -1,16 +1,31 @@
 use std::{fmt::Debug, iter::from_fn};
 
-use crate::player::Property;
+use crate::{player::Property, state::Rules};
 
 use super::{Card, Color, Number};
 
-const NUMBER_OF_COLORS: u32 = 5;
-const NUMBER_OF_NUMBERS: u32 = 5;
+const NUMBER_OF_COLORS: u32 = Color::ALL.len() as u32;
+const NUMBER_OF_NUMBERS: u32 = Number::ALL.len() as u32;
 const NUMBER_OF_CARDS: u32 = NUMBER_OF_NUMBERS * NUMBER_OF_COLORS;
+const _: () = assert!(NUMBER_OF_CARDS <= u64::BITS);
+
+/// The bits of all numbers of a color, shifted by the index of the color.
+const COLOR_MASK: u64 = (1 << NUMBER_OF_NUMBERS) - 1;
+/// The bits of the ones of every color, shifted by the index of the number.
+const NUMBER_MASK: u64 = {
+    let mut mask = 0;
+    let mut color_index = 0;
+    while color_index < NUMBER_OF_COLORS {
+        mask |= 1 << (NUMBER_OF_NUMBERS * color_index);
+        color_index += 1;
+    }
+    mask
+};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CardSet {
-    bits: u32,
+    bits: u64,
 }
 
 impl Debug for CardSet {
@@ -23,74 +38,55 @@ impl Debug for CardSet {
 }
 
 fn card_to_index(card: Card) -> u32 {
-    let color_index = match card.color {
-        Color::White => 0,
-        Color::Green => 1,
-        Color::Yellow => 2,
-        Color::Red => 3,
-        Color::Blue => 4,
-    };
-
-    let number_index = match card.number {
-        Number::One => 0,
-        Number::Two => 1,
-        Number::Three => 2,
-        Number::Four => 3,
-        Number::Five => 4,
-    };
-
-    let index = NUMBER_OF_NUMBERS * color_index + number_index;
+    let index = NUMBER_OF_NUMBERS * card.color.index() as u32 + card.number.index() as u32;
     assert!(index < NUMBER_OF_CARDS);
     index
 }
 
 fn index_to_card(index: u32) -> Card {
     assert!(index < NUMBER_OF_CARDS);
-    let color = match index / NUMBER_OF_NUMBERS {
-        0 => Color::White,
-        1 => Color::Green,
-        2 => Color::Yellow,
-        3 => Color::Red,
-        4 => Color::Blue,
-        _ => unreachable!(),
-    };
-
-    let number = match index % NUMBER_OF_NUMBERS {
-        0 => Number::One,
-        1 => Number::Two,
-        2 => Number::Three,
-        3 => Number::Four,
-        4 => Number::Five,
-        _ => unreachable!(),
-    };
-
-    Card { number, color }
+    Card {
+        number: Number::ALL[(index % NUMBER_OF_NUMBERS) as usize],
+        color: Color::ALL[(index / NUMBER_OF_NUMBERS) as usize],
+    }
 }
 
-#[allow(clippy::unusual_byte_groupings)]
 impl CardSet {
     pub fn none() -> Self {
-        Self {
-            bits: 0b00_00000_00000_00000_00000_00000_00000,
-        }
+        Self { bits: 0 }
     }
 
     pub fn is_empty(&self) -> bool {
         self.bits == 0
     }
 
+    /// Every card of every color, see [`CardSet::all_in`] for the cards of a game.
     pub fn all() -> Self {
         Self {
-            bits: 0b00_00000_11111_11111_11111_11111_11111,
+            bits: (1 << NUMBER_OF_CARDS) - 1,
+        }
+    }
+
+    /// Every card that is in the deck with `rules`.
+    pub fn all_in(rules: &Rules) -> Self {
+        let mut result = Self::none();
+        for color in rules.used_colors() {
+            for number in Number::ALL {
+                let card = Card { number, color };
+                if rules.copies(card) > 0 {
+                    result.add(card);
+                }
+            }
         }
+        result
     }
 
     pub fn len(&self) -> u32 {
         self.bits.count_ones()
     }
 
-    pub fn apply(&mut self, hinted_property: Property) {
-        self.intersect(&Self::with_property(hinted_property));
+    pub fn apply(&mut self, hinted_property: Property, rules: &Rules) {
+        self.intersect(&Self::with_property(hinted_property, rules));
     }
 
     pub(crate) fn extend(&mut self, other: Self) {
@@ -110,20 +106,28 @@ impl CardSet {
         !already_there
     }
 
-    pub(crate) fn with_property(hinted_property: Property) -> Self {
-        let data = match hinted_property {
-            Property::Color(Color::White) => 0b00_00000_00000_00000_00000_00000_11111,
-            Property::Color(Color::Green) => 0b00_00000_00000_00000_00000_11111_00000,
-            Property::Color(Color::Yellow) => 0b00_00000_00000_00000_11111_00000_00000,
-            Property::Color(Color::Red) => 0b00_00000_00000_11111_00000_00000_00000,
-            Property::Color(Color::Blue) => 0b00_00000_11111_00000_00000_00000_00000,
-            Property::Number(Number::One) => 0b00_00000_00001_00001_00001_00001_00001,
-            Property::Number(Number::Two) => 0b00_00000_00010_00010_00010_00010_00010,
-            Property::Number(Number::Three) => 0b00_00000_00100_00100_00100_00100_00100,
-            Property::Number(Number::Four) => 0b00_00000_01000_01000_01000_01000_01000,
-            Property::Number(Number::Five) => 0b00_00000_10000_10000_10000_10000_10000,
-        };
-        Self { bits: data }
+    fn of_color(color: Color) -> Self {
+        Self {
+            bits: COLOR_MASK << (NUMBER_OF_NUMBERS * color.index() as u32),
+        }
+    }
+
+    /// The cards touched by a hint, see [`Rules::touches`].
+    pub(crate) fn with_property(hinted_property: Property, rules: &Rules) -> Self {
+        match hinted_property {
+            Property::Color(hinted_color) => {
+                let mut result = Self::none();
+                for color in Color::ALL {
+                    if rules.touches(hinted_color, color) {
+                        result.merge(&Self::of_color(color));
+                    }
+                }
+                result
+            }
+            Property::Number(number) => Self {
+                bits: NUMBER_MASK << number.index(),
+            },
+        }
     }
 
     pub(crate) fn intersect(&mut self, other: &CardSet) {
@@ -138,8 +142,8 @@ impl CardSet {
         self.intersects(&Self::just(card))
     }
 
-    pub(crate) fn apply_not(&mut self, hinted_property: Property) {
-        self.exclude(&Self::with_property(hinted_property));
+    pub(crate) fn apply_not(&mut self, hinted_property: Property, rules: &Rules) {
+        self.exclude(&Self::with_property(hinted_property, rules));
     }
 
     pub(crate) fn remove(&mut self, card: Card) -> bool {
@@ -185,40 +189,80 @@ impl CardSet {
 #[cfg(test)]
 mod tests {
     use crate::{
+        card::{Color, Number},
         player::Property,
         state::{Firework, Rules},
     };
 
     use super::CardSet;
 
+    fn variants() -> Vec<Rules> {
+        let mut result = Vec::new();
+        for number_of_suits in [5, 6] {
+            for rainbow in [false, true] {
+                let mut rules = Rules::new();
+                rules.number_of_suits = number_of_suits;
+                rules.rainbow = rainbow;
+                result.push(rules);
+            }
+        }
+        result
+    }
+
     #[test]
     fn properties_work_correctly() {
-        for property in Property::all(&Rules::new()) {
-            let pos = CardSet::with_property(property);
-            let mut neg = CardSet::all();
-            neg.apply_not(property);
-
-            for card in pos.iter() {
-                assert!(card.satisfies(property));
-            }
-            for card in neg.iter() {
-                assert!(!card.satisfies(property));
+        for rules in variants() {
+            let all = CardSet::all_in(&rules);
+            let size = 5 * rules.number_of_suits as u32;
+            assert_eq!(all.len(), size);
+
+            for property in Property::all(&rules) {
+                let mut pos = CardSet::with_property(property, &rules);
+                pos.intersect(&all);
+                let mut neg = all;
+                neg.apply_not(property, &rules);
+
+                for card in pos.iter() {
+                    assert!(card.satisfies(property, &rules));
+                }
+                for card in neg.iter() {
+                    assert!(!card.satisfies(property, &rules));
+                }
+
+                let touched = match property {
+                    Property::Color(_) if rules.rainbow => 10,
+                    Property::Color(_) => 5,
+                    Property::Number(_) => size / 5,
+                };
+                assert_eq!(pos.len(), touched);
+                assert_eq!(neg.len(), size - touched);
+                assert!(!pos.intersects(&neg));
             }
+        }
+    }
 
-            assert_eq!(pos.len(), 5);
-            assert_eq!(neg.len(), 20);
-            assert!(!pos.intersects(&neg));
+    #[test]
+    fn every_card_has_its_own_index() {
+        let all = CardSet::all();
+        assert_eq!(all.len() as usize, Number::ALL.len() * Color::ALL.len());
+        for (card, &color) in all.iter().step_by(Number::ALL.len()).zip(&Color::ALL) {
+            assert_eq!(card.color, color);
         }
+        assert!(CardSet::all_in(&Rules::new())
+            .iter()
+            .all(|card| all.contains(card)));
     }
 
     #[test]
     fn play_order() {
-        let mut firework = Firework::new(&Rules::new().used_colors());
+        for rules in variants() {
+            let mut firework = Firework::new(&rules.used_colors());
 
-        for card in CardSet::all().in_play_order() {
-            let succ = firework.add(card);
-            assert!(succ)
+            for card in CardSet::all_in(&rules).in_play_order() {
+                let succ = firework.add(card);
+                assert!(succ)
+            }
+            assert!(firework.is_complete())
         }
-        assert!(firework.is_complete())
     }
 }
@@ -0,0 +1,393 @@
+//! Conversion between [`Record`]s and the JSON game format of hanab.live.
+//!
+//! hanab.live identifies cards by their order in the deck, starting at 0 for the first card drawn.
+//! Our white suit takes the place of purple, so standard games are played as "No Variant" there.
+//! Teal is the sixth suit of "6 Suits", and the rainbow or dark suit replaces the last suit as in
+//! "Rainbow (5 Suits)" or "Black (6 Suits)".
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    card::{Card, Color, Number},
+    player::{action::Action, player_name, Property},
+    state::{
+        deck::Deck,
+        record::{Record, ReplayError},
+        Rules,
+    },
+};
+
+/// The number of suits and whether the rainbow suit, the dark suit and up-or-down piles are used.
+type VariantRules = (usize, bool, bool, bool);
+
+const VARIANTS: [(&str, VariantRules); 8] = [
+    ("No Variant", (5, false, false, false)),
+    ("6 Suits", (6, false, false, false)),
+    ("Rainbow (5 Suits)", (5, true, false, false)),
+    ("Rainbow (6 Suits)", (6, true, false, false)),
+    ("Black (5 Suits)", (5, false, true, false)),
+    ("Black (6 Suits)", (6, false, true, false)),
+    ("Up or Down (5 Suits)", (5, false, false, true)),
+    ("Up or Down (6 Suits)", (6, false, false, true)),
+];
+
+/// The rank hanab.live uses for start cards.
+const START_RANK: usize = 7;
+
+/// The hanab.live variant of `rules`. Apart from the suits, every rule has to be the default
+/// for the number of players, because hanab.live games are imported that way.
+fn variant(rules: &Rules) -> Result<&'static str, HanabLiveError> {
+    let unsupported = || HanabLiveError::UnsupportedRules(Box::new(*rules));
+    if !(2..=6).contains(&rules.number_of_players) {
+        return Err(unsupported());
+    }
+    let mut default_rules = Rules::with_number_of_players(rules.number_of_players);
+    default_rules.number_of_suits = rules.number_of_suits;
+    default_rules.rainbow = rules.rainbow;
+    default_rules.dark = rules.dark;
+    default_rules.up_or_down = rules.up_or_down;
+    if *rules != default_rules {
+        return Err(unsupported());
+    }
+
+    let variant_rules = (
+        rules.number_of_suits,
+        rules.rainbow,
+        rules.dark,
+        rules.up_or_down,
+    );
+    VARIANTS
+        .iter()
+        .find(|(_, v)| *v == variant_rules)
+        .map(|&(variant, _)| variant)
+        .ok_or_else(unsupported)
+}
+
+const PLAY: u8 = 0;
+const DISCARD: u8 = 1;
+const COLOR_CLUE: u8 = 2;
+const RANK_CLUE: u8 = 3;
+const GAME_OVER: u8 = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HanabLiveGame {
+    pub players: Vec<String>,
+    pub deck: Vec<HanabLiveCard>,
+    pub actions: Vec<HanabLiveAction>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<HanabLiveOptions>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HanabLiveCard {
+    pub suit_index: usize,
+    pub rank: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HanabLiveAction {
+    #[serde(rename = "type")]
+    pub action_type: u8,
+    pub target: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HanabLiveOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum HanabLiveError {
+    Json(serde_json::Error),
+    UnsupportedVariant(String),
+    UnsupportedNumberOfPlayers(usize),
+    /// The rules of an exported record have no hanab.live equivalent, like rainbow and dark
+    /// suits combined or a different number of strikes.
+    UnsupportedRules(Box<Rules>),
+    UnknownCard(HanabLiveCard),
+    /// The action at `index` (starting at 0) could not be converted.
+    InvalidAction {
+        index: usize,
+        action: HanabLiveAction,
+    },
+    Replay(ReplayError),
+}
+
+impl Display for HanabLiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HanabLiveError::Json(error) => write!(f, "invalid JSON: {error}"),
+            HanabLiveError::UnsupportedVariant(variant) => {
+                write!(f, "unsupported variant \"{variant}\"")
+            }
+            HanabLiveError::UnsupportedNumberOfPlayers(number) => {
+                write!(f, "unsupported number of players: {number}")
+            }
+            HanabLiveError::UnsupportedRules(_) => {
+                write!(f, "hanab.live has no variant with these rules")
+            }
+            HanabLiveError::UnknownCard(card) => write!(
+                f,
+                "unknown card with suit index {} and rank {}",
+                card.suit_index, card.rank
+            ),
+            HanabLiveError::InvalidAction { index, action } => {
+                write!(f, "invalid action {index}: {action:?}")
+            }
+            HanabLiveError::Replay(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for HanabLiveError {}
+
+impl From<serde_json::Error> for HanabLiveError {
+    fn from(error: serde_json::Error) -> Self {
+        HanabLiveError::Json(error)
+    }
+}
+
+/// The suits in the order of hanab.live.
+fn suits(rules: &Rules) -> Vec<Color> {
+    let mut suits = vec![
+        Color::Red,
+        Color::Yellow,
+        Color::Green,
+        Color::Blue,
+        Color::White,
+        Color::Teal,
+    ];
+    rules.replace_special_suits(&mut suits);
+    suits
+}
+
+/// The colors of color clues in the order of hanab.live, which skips rainbow.
+fn clue_colors(rules: &Rules) -> Vec<Color> {
+    suits(rules)
+        .into_iter()
+        .filter(|color| color.is_clue_color())
+        .collect()
+}
+
+fn to_hanab_live_card(rules: &Rules, card: Card) -> HanabLiveCard {
+    HanabLiveCard {
+        suit_index: suits(rules).iter().position(|&c| c == card.color).unwrap(),
+        rank: rank(card.number),
+    }
+}
+
+fn rank(number: Number) -> usize {
+    match number {
+        Number::Start => START_RANK,
+        number => number.score(),
+    }
+}
+
+fn number_from_rank(rank_to_find: usize) -> Option<Number> {
+    Number::ALL
+        .into_iter()
+        .find(|&number| rank(number) == rank_to_find)
+}
+
+fn from_hanab_live_card(rules: &Rules, card: HanabLiveCard) -> Option<Card> {
+    Some(Card {
+        color: *suits(rules).get(card.suit_index)?,
+        number: number_from_rank(card.rank)?,
+    })
+}
+
+/// The orders of the cards in each hand, newest card first.
+struct Hands {
+    orders: Vec<Vec<usize>>,
+    next_order: usize,
+    deck_size: usize,
+}
+
+impl Hands {
+    fn deal(rules: &Rules, deck_size: usize) -> Self {
+        let mut hands = Self {
+            orders: vec![Vec::new(); rules.number_of_players],
+            next_order: 0,
+            deck_size,
+        };
+        for seat in 0..rules.number_of_players {
+            for _ in 0..rules.hand_size {
+                hands.draw(seat);
+            }
+        }
+        hands
+    }
+
+    fn draw(&mut self, seat: usize) {
+        if self.next_order < self.deck_size {
+            self.orders[seat].insert(0, self.next_order);
+            self.next_order += 1;
+        }
+    }
+}
+
+/// Fails if hanab.live has no variant with the rules of the record.
+pub fn export(record: &Record) -> Result<HanabLiveGame, HanabLiveError> {
+    let rules = record.rules();
+    let variant = variant(rules)?;
+    let draw_order: Vec<Card> = record.deck().cards().iter().rev().copied().collect();
+    let mut hands = Hands::deal(rules, draw_order.len());
+
+    let mut actions = Vec::with_capacity(record.actions().len());
+
+    for turn in record.turns() {
+        let action = match turn.action {
+            Action::Play { position, .. } | Action::Discard { position, .. } => {
+                let order = hands.orders[turn.seat].remove(position - 1);
+                hands.draw(turn.seat);
+                HanabLiveAction {
+                    action_type: if matches!(turn.action, Action::Play { .. }) {
+                        PLAY
+                    } else {
+                        DISCARD
+                    },
+                    target: order,
+                    value: None,
+                }
+            }
+            Action::Hint {
+                receiver,
+                hinted_property,
+                ..
+            } => {
+                let (action_type, value) = match hinted_property {
+                    Property::Color(color) => (
+                        COLOR_CLUE,
+                        clue_colors(rules).iter().position(|&c| c == color).unwrap(),
+                    ),
+                    Property::Number(number) => (RANK_CLUE, number.score()),
+                };
+                HanabLiveAction {
+                    action_type,
+                    target: receiver,
+                    value: Some(value),
+                }
+            }
+        };
+        actions.push(action);
+    }
+
+    Ok(HanabLiveGame {
+        players: (0..rules.number_of_players)
+            .map(|seat| player_name(seat).into_owned())
+            .collect(),
+        deck: draw_order
+            .into_iter()
+            .map(|card| to_hanab_live_card(rules, card))
+            .collect(),
+        actions,
+        options: (variant != VARIANTS[0].0).then(|| HanabLiveOptions {
+            variant: Some(variant.to_string()),
+        }),
+    })
+}
+
+pub fn to_json(record: &Record) -> Result<String, HanabLiveError> {
+    Ok(serde_json::to_string(&export(record)?)?)
+}
+
+/// Converts a hanab.live game. Unfinished games are fine, but every action has to be legal.
+pub fn import(game: &HanabLiveGame) -> Result<Record, HanabLiveError> {
+    let variant = game
+        .options
+        .as_ref()
+        .and_then(|o| o.variant.as_deref())
+        .unwrap_or(VARIANTS[0].0);
+    let Some(&(_, (number_of_suits, rainbow, dark, up_or_down))) =
+        VARIANTS.iter().find(|(v, _)| *v == variant)
+    else {
+        return Err(HanabLiveError::UnsupportedVariant(variant.to_string()));
+    };
+
+    let number_of_players = game.players.len();
+    if !(2..=6).contains(&number_of_players) {
+        return Err(HanabLiveError::UnsupportedNumberOfPlayers(
+            number_of_players,
+        ));
+    }
+    let mut rules = Rules::with_number_of_players(number_of_players);
+    rules.number_of_suits = number_of_suits;
+    rules.rainbow = rainbow;
+    rules.dark = dark;
+    rules.up_or_down = up_or_down;
+
+    let draw_order = game
+        .deck
+        .iter()
+        .map(|&card| from_hanab_live_card(&rules, card).ok_or(HanabLiveError::UnknownCard(card)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut hands = Hands::deal(&rules, draw_order.len());
+    let mut actions = Vec::with_capacity(game.actions.len());
+
+    for (index, &action) in game.actions.iter().enumerate() {
+        let invalid = || HanabLiveError::InvalidAction { index, action };
+        let seat = index % number_of_players;
+
+        let converted = match (action.action_type, action.value) {
+            (PLAY | DISCARD, _) => {
+                let Some(hand_index) = hands.orders[seat].iter().position(|&o| o == action.target)
+                else {
+                    return Err(invalid());
+                };
+                hands.orders[seat].remove(hand_index);
+                hands.draw(seat);
+
+                if action.action_type == PLAY {
+                    Action::Play {
+                        card: None,
+                        position: hand_index + 1,
+                    }
+                } else {
+                    Action::Discard {
+                        card: None,
+                        position: hand_index + 1,
+                    }
+                }
+            }
+            (COLOR_CLUE | RANK_CLUE, Some(value)) => {
+                let hinted_property = if action.action_type == COLOR_CLUE {
+                    Property::Color(*clue_colors(&rules).get(value).ok_or_else(invalid)?)
+                } else {
+                    Property::Number(number_from_rank(value).ok_or_else(invalid)?)
+                };
+                let Some(hand) = hands.orders.get(action.target) else {
+                    return Err(invalid());
+                };
+                let cards: Vec<Card> = hand.iter().map(|&order| draw_order[order]).collect();
+
+                Action::hint(action.target, hinted_property, &cards, &rules)
+            }
+            (GAME_OVER, _) if index + 1 == game.actions.len() => break,
+            _ => return Err(invalid()),
+        };
+
+        actions.push(converted);
+    }
+
+    let record = Record::new(
+        rules,
+        Deck::new(draw_order.into_iter().rev().collect()),
+        actions,
+    );
+
+    match record.replay() {
+        Ok(_) | Err(ReplayError::Unfinished { .. }) => Ok(record),
+        Err(error) => Err(HanabLiveError::Replay(error)),
+    }
+}
+
+pub fn from_json(json: &str) -> Result<Record, HanabLiveError> {
+    import(&serde_json::from_str(json)?)
+}
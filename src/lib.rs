@@ -1,3 +1,9 @@
 pub mod card;
+#[cfg(feature = "serde")]
+pub mod hanab_live;
 pub mod player;
+pub mod simulate;
+pub mod solver;
 pub mod state;
+#[cfg(feature = "tui")]
+pub mod viewer;
@@ -1,24 +1,359 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    thread,
+};
+
+use clap::{Parser, Subcommand, ValueEnum};
 use rand::SeedableRng;
 use rand_chacha::ChaCha20Rng;
+use serde_json::json;
 
 use ranabi::{
-    player::{basic::BasicPlayer, Player},
-    state::{play_game, Rules},
+    hanab_live,
+    simulate::{simulate, simulate_with_ceilings},
+    solver::Budget,
+    state::{deck::Deck, record::Record, record_game, Rules},
 };
 
-fn main() {
-    println!("Hello, world!");
+#[cfg(feature = "tui")]
+use ranabi::viewer::{self, Viewer};
 
-    let rules = Rules::new();
+#[derive(Parser)]
+#[command(version, about = "Hanabi engine and bot")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    #[arg(long, value_enum, global = true, default_value_t = Format::Human)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Human,
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Play a single game with the basic player and print its record.
+    Play {
+        #[arg(long, default_value_t = 666, conflicts_with = "deck")]
+        seed: u64,
+        /// A deck line as printed by `deck generate`, drawn from the end.
+        #[arg(long)]
+        deck: Option<String>,
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(2..=6))]
+        players: u8,
+        /// Read the rules from a TOML file instead of using the standard rules.
+        #[arg(long, conflicts_with = "players")]
+        rules: Option<PathBuf>,
+        /// Save the record as JSON, to be read by `replay`.
+        #[arg(long)]
+        save: Option<PathBuf>,
+    },
+    /// Play many games and print aggregated statistics.
+    Sim {
+        #[arg(long, default_value_t = 1000)]
+        games: u64,
+        /// Defaults to the available parallelism.
+        #[arg(long)]
+        threads: Option<usize>,
+        #[arg(long, default_value_t = 42069)]
+        seed: u64,
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(2..=6))]
+        players: u8,
+        /// Read the rules from a TOML file instead of using the standard rules.
+        #[arg(long, conflicts_with = "players")]
+        rules: Option<PathBuf>,
+        /// Also solve every deck to compare the scores with the best score the deck allows.
+        #[arg(long)]
+        ceilings: bool,
+        /// How many states the solver may visit for each deck.
+        #[arg(long, default_value_t = Budget::default().nodes, requires = "ceilings")]
+        solver_nodes: usize,
+    },
+    /// Validate and render a saved record.
+    Replay {
+        file: PathBuf,
+        /// Read the file as a hanab.live game export instead of a ranabi record.
+        #[arg(long)]
+        hanab_live: bool,
+    },
+    /// Step through a saved record interactively.
+    #[cfg(feature = "tui")]
+    View {
+        file: PathBuf,
+        /// Read the file as a hanab.live game export instead of a ranabi record.
+        #[arg(long)]
+        hanab_live: bool,
+        /// Start turn, starting at 1.
+        #[arg(long, default_value_t = 1)]
+        turn: usize,
+        /// Hide the cards of this seat, starting at 0.
+        #[arg(long)]
+        seat: Option<usize>,
+    },
+    /// Generate or validate deck lines.
+    Deck {
+        #[command(subcommand)]
+        command: DeckCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum DeckCommand {
+    /// Print a shuffled deck.
+    Generate {
+        #[arg(long, default_value_t = 666)]
+        seed: u64,
+    },
+    /// Check that a line contains exactly the cards of a deck.
+    Check { line: String },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Play {
+            seed,
+            deck,
+            players,
+            rules,
+            save,
+        } => read_rules(rules, players.into())
+            .and_then(|rules| play(cli.format, seed, deck, rules, save)),
+        Command::Sim {
+            games,
+            threads,
+            seed,
+            players,
+            rules,
+            ceilings,
+            solver_nodes,
+        } => read_rules(rules, players.into()).and_then(|rules| {
+            let budget = ceilings.then_some(Budget::new(solver_nodes));
+            sim(cli.format, games, threads, seed, rules, budget)
+        }),
+        Command::Replay { file, hanab_live } => replay(cli.format, file, hanab_live),
+        #[cfg(feature = "tui")]
+        Command::View {
+            file,
+            hanab_live,
+            turn,
+            seat,
+        } => view(file, hanab_live, turn, seat),
+        Command::Deck { command } => deck(cli.format, command),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            match cli.format {
+                Format::Human => eprintln!("Error: {error}"),
+                Format::Json => println!("{}", json!({ "error": error })),
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn read_rules(file: Option<PathBuf>, players: usize) -> Result<Rules, String> {
+    match file {
+        Some(file) => {
+            let toml = fs::read_to_string(&file).map_err(|e| format!("{}: {e}", file.display()))?;
+            Rules::from_toml(&toml).map_err(|e| format!("{}: {e}", file.display()))
+        }
+        None => Ok(Rules::with_number_of_players(players)),
+    }
+}
+
+fn play(
+    format: Format,
+    seed: u64,
+    deck: Option<String>,
+    rules: Rules,
+    save: Option<PathBuf>,
+) -> Result<(), String> {
+    let deck = match deck {
+        Some(line) => Deck::parse_line(&line, Some(&rules)).map_err(|e| e.to_string())?,
+        None => rules.get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(seed)),
+    };
+
+    let (score, record, _) =
+        record_game(rules, deck, rules.get_basic_player()).map_err(|e| e.to_string())?;
+
+    if let Some(path) = save {
+        fs::write(&path, record.to_json()).map_err(|e| format!("{}: {e}", path.display()))?;
+    }
+
+    match format {
+        Format::Human => {
+            print!("{record}");
+            println!("Deck: {}", record.deck().to_line());
+        }
+        Format::Json => println!(
+            "{}",
+            json!({ "score": score, "max_score": rules.max_score(), "record": record })
+        ),
+    }
 
-    let players = (0..rules.number_of_players)
-        .map(|id| Box::new(BasicPlayer::new(rules, id)) as Box<dyn Player>)
-        .collect();
+    Ok(())
+}
+
+fn sim(
+    format: Format,
+    games: u64,
+    threads: Option<usize>,
+    seed: u64,
+    rules: Rules,
+    budget: Option<Budget>,
+) -> Result<(), String> {
+    if games == 0 {
+        return Err("at least one game has to be simulated".to_string());
+    }
+
+    let threads = threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+        .max(1);
+
+    let players = |rules: Rules| rules.get_basic_player();
+    let statistics = match budget {
+        Some(budget) => simulate_with_ceilings(rules, players, seed, 0..games, threads, budget),
+        None => simulate(rules, players, seed, 0..games, threads),
+    };
+
+    match format {
+        // At least one game was simulated, so every average exists.
+        Format::Human => {
+            println!("Games: {}", statistics.games);
+            println!(
+                "Average: {:.3}/{}",
+                statistics.mean_score().unwrap(),
+                rules.max_score()
+            );
+            if budget.is_some() {
+                println!(
+                    "Ceiling: {:.3}/{} ({:.2}% of it scored, {} games at the ceiling, {} unproven)",
+                    statistics.mean_ceiling().unwrap(),
+                    rules.max_score(),
+                    100. * statistics.score_relative_to_ceiling().unwrap(),
+                    statistics.games_at_ceiling,
+                    statistics.unproven_ceilings.len()
+                );
+            }
+            println!(
+                "Strikeouts: {:.2}%",
+                100. * statistics.strikeout_rate().unwrap()
+            );
+            if !statistics.rule_violations.is_empty() {
+                println!("Aborted: {}", statistics.rule_violations.len());
+            }
+            for (score, &number) in statistics.score_histogram.iter().enumerate() {
+                if number > 0 {
+                    println!("{score:>2}: {number}");
+                }
+            }
+            println!(
+                "Average time per decision: {:.1} µs",
+                statistics.time_per_decision().as_secs_f64() * 1000. * 1000.
+            );
+        }
+        Format::Json => println!(
+            "{}",
+            json!({
+                "mean_score": statistics.mean_score(),
+                "mean_ceiling": budget.and(statistics.mean_ceiling()),
+                "strikeout_rate": statistics.strikeout_rate(),
+                "statistics": statistics,
+            })
+        ),
+    }
 
-    let conclusion = play_game(Rules::new(), &mut ChaCha20Rng::seed_from_u64(666), players);
-    if let Some(score) = conclusion {
-        println!("Won with score {}/{}.", score, rules.max_score());
+    Ok(())
+}
+
+fn read_record(file: &Path, is_hanab_live: bool) -> Result<Record, String> {
+    let json = fs::read_to_string(file).map_err(|e| format!("{}: {e}", file.display()))?;
+    if is_hanab_live {
+        hanab_live::from_json(&json).map_err(|e| e.to_string())
     } else {
-        println!("Lost.")
+        Record::from_json(&json).map_err(|e| e.to_string())
+    }
+}
+
+fn replay(format: Format, file: PathBuf, is_hanab_live: bool) -> Result<(), String> {
+    let record = read_record(&file, is_hanab_live)?;
+
+    match format {
+        Format::Human => print!("{record}"),
+        Format::Json => {
+            let turns: Vec<_> = record
+                .turns()
+                .map(|turn| {
+                    json!({
+                        "turn": turn.turn,
+                        "seat": turn.seat,
+                        "action": turn.action,
+                        "revealed": turn.revealed,
+                        "drawn": turn.drawn,
+                    })
+                })
+                .collect();
+            let outcome = match record.replay() {
+                Ok(score) => json!({ "score": score }),
+                Err(error) => json!({ "error": error.to_string() }),
+            };
+            println!("{}", json!({ "outcome": outcome, "turns": turns }));
+        }
     }
+
+    Ok(())
+}
+
+#[cfg(feature = "tui")]
+fn view(
+    file: PathBuf,
+    is_hanab_live: bool,
+    turn: usize,
+    seat: Option<usize>,
+) -> Result<(), String> {
+    let record = read_record(&file, is_hanab_live)?;
+    if seat.is_some_and(|seat| seat >= record.rules().number_of_players) {
+        return Err(format!("there is no seat {}", seat.unwrap()));
+    }
+
+    let mut viewer = Viewer::new(&record).ok_or("not enough cards to deal")?;
+    viewer.jump_to(turn);
+    viewer.set_point_of_view(seat);
+
+    viewer::run(viewer).map_err(|e| e.to_string())
+}
+
+fn deck(format: Format, command: DeckCommand) -> Result<(), String> {
+    let rules = Rules::new();
+
+    match command {
+        DeckCommand::Generate { seed } => {
+            let line = rules
+                .get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(seed))
+                .to_line();
+            match format {
+                Format::Human => println!("{line}"),
+                Format::Json => println!("{}", json!({ "deck": line })),
+            }
+        }
+        DeckCommand::Check { line } => {
+            Deck::parse_line(&line, Some(&rules)).map_err(|e| e.to_string())?;
+            match format {
+                Format::Human => println!("Valid."),
+                Format::Json => println!("{}", json!({ "valid": true })),
+            }
+        }
+    }
+
+    Ok(())
 }
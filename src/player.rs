@@ -1,4 +1,4 @@
-use std::{fmt::Display, ops::RangeInclusive};
+use std::{borrow::Cow, fmt::Display, ops::RangeInclusive};
 
 use crate::{
     card::{Card, Color, Number},
@@ -8,6 +8,8 @@ use crate::{
 use self::action::Action;
 
 pub trait Player {
+    /// Called once before the starting hands are dealt, with the number of cards in the deck.
+    fn witness_deck_size(&mut self, _deck_size: usize) {}
     fn witness_action(&mut self, action: Action, player: usize);
     fn witness_draw(&mut self, player: usize, card: Option<Card>);
     fn request_action(&self) -> Action;
@@ -47,19 +49,23 @@ impl Display for Action {
     }
 }
 
-fn player_name(player_id: usize) -> &'static str {
+/// Seats beyond the sixth, as in externally built actions, are called by their number.
+pub(crate) fn player_name(player_id: usize) -> Cow<'static, str> {
     match player_id {
-        0 => "Alice",
-        1 => "Bob",
-        2 => "Cathy",
-        3 => "Donald",
-        _ => todo!(),
+        0 => "Alice".into(),
+        1 => "Bob".into(),
+        2 => "Cathy".into(),
+        3 => "Donald".into(),
+        4 => "Emily".into(),
+        5 => "Frank".into(),
+        _ => format!("Player {player_id}").into(),
     }
 }
 
 pub mod action;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Property {
     Color(Color),
     Number(Number),
@@ -75,28 +81,86 @@ impl Display for Property {
 }
 
 impl Property {
+    /// Every property that can be hinted with `rules`.
     pub fn all(rules: &Rules) -> Vec<Self> {
-        let mut result = Vec::with_capacity(5 + rules.used_colors().len());
-        result.push(Property::Number(Number::One));
-        result.push(Property::Number(Number::Two));
-        result.push(Property::Number(Number::Three));
-        result.push(Property::Number(Number::Four));
-        result.push(Property::Number(Number::Five));
-        for color in rules.used_colors() {
+        let clue_colors = rules.clue_colors();
+        let mut result = Vec::with_capacity(Number::ALL.len() + clue_colors.len());
+        for number in Number::ALL {
+            if number != Number::Start {
+                result.push(Property::Number(number));
+            }
+        }
+        for color in clue_colors {
             result.push(Property::Color(color));
         }
+        result.retain(|&property| rules.allows(property));
         result
     }
 }
 
 pub mod basic;
+pub mod hands;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "UncheckedPositionSet")
+)]
 pub struct PositionSet {
     positions: [bool; 6],
     hand_size: usize,
 }
 
+/// A [`PositionSet`] as read, before checking that it fits its hand.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedPositionSet {
+    positions: [bool; 6],
+    hand_size: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedPositionSet> for PositionSet {
+    type Error = InvalidPosition;
+
+    fn try_from(set: UncheckedPositionSet) -> Result<Self, Self::Error> {
+        let mut result = Self::try_new(set.hand_size)?;
+        for (position, &contained) in set.positions.iter().enumerate() {
+            if contained {
+                result.try_insert(position)?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Error when a [`PositionSet`] would not fit its hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidPosition {
+    /// Hands have at most [`PositionSet::MAX_HAND_SIZE`] cards.
+    HandSize(usize),
+    /// Positions are between 1 and the hand size.
+    Position { position: usize, hand_size: usize },
+}
+
+impl Display for InvalidPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidPosition::HandSize(hand_size) => write!(f, "hand size {hand_size} is too big"),
+            InvalidPosition::Position {
+                position,
+                hand_size,
+            } => write!(
+                f,
+                "position {position} is not in a hand of {hand_size} cards"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidPosition {}
+
 impl Display for PositionSet {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         fn to_char(b: bool) -> char {
@@ -115,11 +179,83 @@ impl Display for PositionSet {
 }
 
 impl PositionSet {
-    fn smallest(&self) -> Option<usize> {
+    /// Position 0 is unused, so the six slots hold hands of up to five cards.
+    pub const MAX_HAND_SIZE: usize = 5;
+
+    /// An empty set for a hand with `hand_size` cards.
+    pub fn try_new(hand_size: usize) -> Result<Self, InvalidPosition> {
+        if hand_size > Self::MAX_HAND_SIZE {
+            return Err(InvalidPosition::HandSize(hand_size));
+        }
+        Ok(Self {
+            positions: [false; 6],
+            hand_size,
+        })
+    }
+
+    pub fn try_from_positions(
+        hand_size: usize,
+        positions: impl IntoIterator<Item = usize>,
+    ) -> Result<Self, InvalidPosition> {
+        let mut result = Self::try_new(hand_size)?;
+        for position in positions {
+            result.try_insert(position)?;
+        }
+        Ok(result)
+    }
+
+    /// Panics if a position is not between 1 and `hand_size`, see
+    /// [`PositionSet::try_from_positions`].
+    pub(crate) fn from_positions(
+        hand_size: usize,
+        positions: impl IntoIterator<Item = usize>,
+    ) -> Self {
+        Self::try_from_positions(hand_size, positions).unwrap()
+    }
+
+    /// The positions of the cards in `hand` (newest first) that satisfy `hinted_property`.
+    pub fn matching(hinted_property: Property, hand: &[Card], rules: &Rules) -> Self {
+        Self::from_positions(
+            hand.len(),
+            (1..).zip(hand).filter_map(|(position, card)| {
+                card.satisfies(hinted_property, rules).then_some(position)
+            }),
+        )
+    }
+
+    /// Fails if `position` is not between 1 and the hand size.
+    pub fn try_insert(&mut self, position: usize) -> Result<(), InvalidPosition> {
+        if !self.all_possible_positions().contains(&position) {
+            return Err(InvalidPosition::Position {
+                position,
+                hand_size: self.hand_size,
+            });
+        }
+        self.positions[position] = true;
+        Ok(())
+    }
+
+    pub fn hand_size(&self) -> usize {
+        self.hand_size
+    }
+
+    /// The contained positions in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.all_possible_positions()
+            .filter(|&id| self.positions[id])
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// The newest contained position.
+    pub fn smallest(&self) -> Option<usize> {
         self.positions.iter().position(|&b| b)
     }
 
-    fn biggest(&self) -> Option<usize> {
+    /// The oldest contained position.
+    pub fn biggest(&self) -> Option<usize> {
         self.positions[self.all_possible_positions()]
             .iter()
             .rev()
@@ -131,7 +267,8 @@ impl PositionSet {
         1..=self.hand_size
     }
 
-    fn inverse(mut self) -> Self {
+    /// All positions of the hand that are not contained.
+    pub fn inverse(mut self) -> Self {
         let all_possible_positions = self.all_possible_positions();
         for b in &mut self.positions[all_possible_positions] {
             *b = !*b;
@@ -140,22 +277,26 @@ impl PositionSet {
         self
     }
 
-    fn focus_position(&self, touched: PositionSet) -> usize {
+    /// The focus of a hint on `self` when the cards in `touched` were touched before:
+    /// the chop if it is newly touched, otherwise the newest newly touched card.
+    /// If nothing is newly touched, the newest card of the hint.
+    /// `None` for a hint that touches no cards, which has no focus.
+    pub fn focus_position(&self, touched: PositionSet) -> Option<usize> {
         assert_eq!(self.hand_size, touched.hand_size);
 
         if self.is_subset_of(touched) {
-            return self.smallest().unwrap();
+            return self.smallest();
         }
 
         let chop = touched.inverse().biggest().unwrap();
         if self.contains(chop) {
-            chop
+            Some(chop)
         } else {
-            self.without(touched).smallest().unwrap()
+            self.without(touched).smallest()
         }
     }
 
-    fn is_subset_of(&self, touched: PositionSet) -> bool {
+    pub fn is_subset_of(&self, touched: PositionSet) -> bool {
         assert_eq!(self.hand_size, touched.hand_size);
         (self.all_possible_positions()).all(|id| touched.contains(id) || !self.contains(id))
     }
@@ -164,7 +305,7 @@ impl PositionSet {
         self.positions[id]
     }
 
-    fn without(mut self, other: PositionSet) -> Self {
+    pub fn without(mut self, other: PositionSet) -> Self {
         assert_eq!(self.hand_size, other.hand_size);
         for id in self.all_possible_positions() {
             self.positions[id] &= !other.positions[id];
@@ -172,11 +313,11 @@ impl PositionSet {
         self
     }
 
-    pub(crate) fn is_empty(&self) -> bool {
+    pub fn is_empty(&self) -> bool {
         !self.positions.iter().any(|&b| b)
     }
 
-    fn is_full(&self) -> bool {
+    pub fn is_full(&self) -> bool {
         self.inverse().is_empty()
     }
 }
@@ -1,8 +1,9 @@
-use crate::card::Card;
+use crate::{card::Card, state::Rules};
 
 use super::{PositionSet, Property};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Action {
     Play {
         card: Option<Card>,
@@ -19,6 +20,20 @@ pub enum Action {
     },
 }
 impl Action {
+    /// A hint at `receiver` whose hand (newest card first) is `receiver_hand`.
+    pub fn hint(
+        receiver: usize,
+        hinted_property: Property,
+        receiver_hand: &[Card],
+        rules: &Rules,
+    ) -> Self {
+        Action::Hint {
+            receiver,
+            hinted_property,
+            positions: PositionSet::matching(hinted_property, receiver_hand, rules),
+        }
+    }
+
     pub(crate) fn add_card_information(&mut self, old: Card) {
         match self {
             Action::Play { card, .. } => {
@@ -1,9 +1,9 @@
 use std::mem::swap;
 
 use crate::{
-    card::{card_set::CardSet, Card, Number},
+    card::{card_set::CardSet, Card},
     player::basic::action_assessment::ActionType,
-    state::{PublicState, Rules},
+    state::{clue_count::ClueCount, PublicState, Rules},
 };
 
 use self::{action_assessment::ActionAssessment, player_state::PlayerState};
@@ -15,11 +15,14 @@ pub struct BasicPlayer {
     witnessed_cards: Vec<Option<Card>>,
     player_id: usize,
     public_state: PublicState,
+    /// The public state with the hidden plays this player could identify, which every decision
+    /// is based on. The same as `public_state` unless plays are hidden.
+    inferred_state: PublicState,
 }
 
 impl BasicPlayer {
     fn rules(&self) -> &Rules {
-        &self.public_state.rules
+        &self.inferred_state.rules
     }
 
     //For now, ignoring cards that can be excluded as all copies are seen elsewhere.
@@ -28,9 +31,9 @@ impl BasicPlayer {
         &self,
         player_id: usize,
     ) -> CardSet {
-        let mut result = CardSet::all();
+        let mut result = CardSet::all_in(self.rules());
 
-        let played = self.public_state.firework.already_played();
+        let played = self.inferred_state.firework.already_played();
         result.exclude(&played);
 
         let touched_in_other_hands = self.touched_in_other_hands_or_less(player_id);
@@ -66,9 +69,9 @@ impl BasicPlayer {
         &self,
         player: usize,
     ) -> CardSet {
-        let mut result = CardSet::all();
+        let mut result = CardSet::all_in(self.rules());
 
-        let definite_trash = self.public_state.definite_trash();
+        let definite_trash = self.inferred_state.definite_trash();
         result.exclude(&definite_trash);
 
         let touched_in_other_hands = self.touched_in_other_hands_or_less(player);
@@ -80,7 +83,7 @@ impl BasicPlayer {
 
     pub fn new(rules: Rules, player_id: usize) -> Self {
         let player_states = (0..rules.number_of_players)
-            .map(|_| PlayerState::new())
+            .map(|_| PlayerState::new(CardSet::all_in(&rules)))
             .collect();
 
         BasicPlayer {
@@ -88,9 +91,25 @@ impl BasicPlayer {
             witnessed_cards: Vec::new(),
             player_id,
             public_state: PublicState::new(rules),
+            inferred_state: PublicState::new(rules),
         }
     }
 
+    /// The cards this player considers possible at `position` of its own hand.
+    pub fn possibilities(&self, position: usize) -> CardSet {
+        self.own_possibilities(position)
+    }
+
+    /// The public information with the hidden plays this player could identify.
+    pub fn inferred_state(&self) -> &PublicState {
+        &self.inferred_state
+    }
+
+    /// The public information as this player witnessed it.
+    pub fn public_state(&self) -> &PublicState {
+        &self.public_state
+    }
+
     fn play_or_discard_card(&mut self, seen_card: Card, player: usize, position: usize) {
         let card_id = self.player_states[player].cards.get(position);
         let info = &mut self.witnessed_cards[card_id];
@@ -117,7 +136,7 @@ impl BasicPlayer {
                 continue;
             };
             let card = self.witnessed_cards[card_id].unwrap();
-            if card.satisfies(hinted_property) {
+            if card.satisfies(hinted_property, self.rules()) {
                 *pos_b = true;
             }
         }
@@ -139,13 +158,13 @@ impl BasicPlayer {
         self_stall_severity: usize,
     ) -> ActionAssessment {
         assert_eq!(positions, self.get_positions(hinted_property, receiver));
-        assert!(!positions.is_empty() || self.rules().allow_null_hints());
+        assert!(!positions.is_empty() || self.rules().allow_null_hints);
         assert_ne!(receiver, self.player_id);
         assert_eq!(
             positions.hand_size,
             self.player_states[receiver].cards.current_hand_size
         );
-        assert_ne!(self.public_state.clues, 0);
+        assert!(self.inferred_state.clues.allows_hint());
         assert_eq!(
             self.player_states[receiver].cards.current_hand_size,
             positions.hand_size
@@ -154,7 +173,7 @@ impl BasicPlayer {
         let interpretations = self.player_states[receiver].get_hint_interpretations(
             hinted_property,
             positions,
-            &self.public_state,
+            &self.inferred_state,
             self_stall_severity,
             all_surely_known_touched_cards_in_hand,
         );
@@ -181,7 +200,7 @@ impl BasicPlayer {
             (self.rules().number_of_players + receiver - self.player_id)
                 % self.rules().number_of_players,
             ActionType::Hint,
-            -1,
+            -(ClueCount::ONE.halves() as i8),
             false,
             0,
         )
@@ -189,7 +208,7 @@ impl BasicPlayer {
 
     fn apply_hypothetical(&self, action: Action, assessment: &mut ActionAssessment) {
         let mut hypothetical_next = self.player_states[self.next_player_id()].clone();
-        let mut hypothetical_state = self.public_state.clone();
+        let mut hypothetical_state = self.inferred_state.clone();
 
         hypothetical_state.apply_action(action);
 
@@ -210,7 +229,7 @@ impl BasicPlayer {
             }
         }
 
-        let next_player_might_be_locked_with_no_clue = hypothetical_state.clues == 0
+        let next_player_might_be_locked_with_no_clue = !hypothetical_state.clues.allows_hint()
             && hypothetical_next.potentially_is_locked_with_no_known_playable_card(
                 &hypothetical_state.firework,
                 &self.potentially_entertained_candidates_for_touched_in_that_players_own_hand(
@@ -248,14 +267,14 @@ impl BasicPlayer {
         let definitely_good_touchable_cards_definitely_known_by_this_player =
             self.definitely_good_touchable_cards_definitely_known_by_this_player(self.player_id);
 
-        for receiver in 0..self.public_state.rules.number_of_players {
+        for receiver in 0..self.inferred_state.rules.number_of_players {
             if receiver == self.player_id {
                 continue;
             }
 
-            for hinted_property in Property::all(&self.public_state.rules) {
+            for hinted_property in Property::all(&self.inferred_state.rules) {
                 let positions = self.get_positions(hinted_property, receiver);
-                if positions.is_empty() {
+                if positions.is_empty() && !self.rules().allow_null_hints {
                     continue;
                 }
 
@@ -342,14 +361,18 @@ impl BasicPlayer {
         (self.player_id + 1) % self.rules().number_of_players
     }
 
-    fn assess_play_this_player(&self, position: usize) -> ActionAssessment {
-        let possibilities = self.this_player().possibilities_self_might_entertain(
+    fn own_possibilities(&self, position: usize) -> CardSet {
+        self.this_player().possibilities_self_might_entertain(
             position,
             &self.potentially_entertained_candidates_for_touched_in_that_players_own_hand(
                 self.player_id,
             ),
             &self.cards_that_player_definitely_sees_all_copies_of(self.player_id),
-        );
+        )
+    }
+
+    fn assess_play_this_player(&self, position: usize) -> ActionAssessment {
+        let possibilities = self.own_possibilities(position);
 
         let is_touched = self.this_player().touched_positions().contains(position);
 
@@ -358,16 +381,18 @@ impl BasicPlayer {
             .is_definitely_aware_that_these_are_all_playable_right_now(
                 &possibilities,
                 is_touched,
-                &self.public_state.firework,
+                &self.inferred_state.firework,
                 &self.touched_in_other_hands_or_more(self.player_id),
             )
         {
-            let sure_influence_on_clue_count =
-                if possibilities.iter().all(|card| card.number == Number::Five) {
-                    1
-                } else {
-                    0
-                };
+            let sure_influence_on_clue_count = if possibilities
+                .iter()
+                .all(|card| self.inferred_state.firework.completes_pile(card))
+            {
+                self.rules().clue_recovery().halves() as i8
+            } else {
+                0
+            };
 
             return ActionAssessment::new(
                 0,
@@ -409,12 +434,19 @@ impl BasicPlayer {
         } else {
             true
         };
-        ActionAssessment::new(0, 0, ActionType::Discard, 1, last_resort, 0)
+        ActionAssessment::new(
+            0,
+            0,
+            ActionType::Discard,
+            self.rules().clue_recovery().halves() as i8,
+            last_resort,
+            0,
+        )
     }
 
     fn cards_that_player_definitely_sees_all_copies_of(&self, player_id: usize) -> CardSet {
-        let mut pile = self.public_state.discard_pile.clone();
-        for card in self.public_state.firework.already_played().iter() {
+        let mut pile = self.inferred_state.discard_pile.clone();
+        for card in self.inferred_state.firework.already_played().iter() {
             pile.add(&card);
         }
 
@@ -431,12 +463,12 @@ impl BasicPlayer {
             }
         }
 
-        pile.full_sets()
+        pile.full_sets(self.rules())
     }
 
     fn stall_severity(&self, player_id: usize) -> usize {
         self.player_states[player_id].stall_severity(
-            &self.public_state,
+            &self.inferred_state,
             &self
                 .potentially_entertained_candidates_for_touched_in_that_players_own_hand(player_id),
             &self.touched_in_other_hands_or_more(player_id),
@@ -476,14 +508,34 @@ impl Player for BasicPlayer {
                 position,
             } => {
                 self.play_or_discard_card(card, action_player, position);
-                self.public_state.play(card);
+                self.inferred_state.apply_action(action);
+                self.public_state.apply_action(action);
             }
             Action::Discard {
                 card: Some(card),
                 position,
             } => {
                 self.play_or_discard_card(card, action_player, position);
-                self.public_state.discard(card);
+                self.inferred_state.apply_action(action);
+                self.public_state.apply_action(action);
+            }
+            Action::Play {
+                card: None,
+                position,
+            } => {
+                //A hidden play. We know the card if we saw it in another hand, or if our own
+                //card could only be one card.
+                let card_id = self.player_states[action_player].cards.get(position);
+                let known = match self.witnessed_cards[card_id] {
+                    Some(card) => Some(card),
+                    None => self.own_possibilities(position).unique(),
+                };
+                self.player_states[action_player].play_or_discard_card(position);
+                self.inferred_state.apply_action(Action::Play {
+                    card: known,
+                    position,
+                });
+                self.public_state.apply_action(action);
             }
             Action::Hint {
                 receiver,
@@ -496,19 +548,27 @@ impl Player for BasicPlayer {
                 self.player_states[receiver].fr_apply_hint(
                     hinted_property,
                     positions,
-                    &self.public_state,
+                    &self.inferred_state,
                     giver_stall_severity,
                     &all_surely_known_touched_cards_in_hands,
                 );
-                self.public_state.hint();
+                self.inferred_state.apply_action(action);
+                self.public_state.apply_action(action);
             }
             _ => unreachable!(),
         }
     }
 
+    fn witness_deck_size(&mut self, deck_size: usize) {
+        self.inferred_state.deck_size = deck_size;
+        self.public_state.deck_size = deck_size;
+    }
+
     fn witness_draw(&mut self, player: usize, card: Option<Card>) {
         let id = self.witnessed_cards.len();
         self.witnessed_cards.push(card);
+        self.inferred_state.witness_draw();
+        self.public_state.witness_draw();
         self.player_states[player].add_card(id)
     }
 
@@ -518,11 +578,11 @@ impl Player for BasicPlayer {
 
         options.extend(self.assess_plays_this_player());
 
-        if self.public_state.clues != 0 {
+        if self.inferred_state.clues.allows_hint() {
             options.extend(self.assess_hints_this_player());
         }
 
-        if self.public_state.clues != self.public_state.rules.max_clues {
+        if self.inferred_state.clues != self.rules().max_clue_count() {
             options.extend(self.assedd_discards_this_player());
         }
 
@@ -4,6 +4,7 @@ pub struct ActionAssessment {
     pub delay_until_relevant: usize,
     is_unconventional: bool,
     pub action_type: ActionType,
+    /// In half clues, so that clue-starved recovery can be expressed.
     pub sure_influence_on_clue_count: i8,
     pub last_resort: bool,
     pub next_player_might_be_locked_with_no_clue: bool,
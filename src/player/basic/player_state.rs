@@ -18,15 +18,18 @@ pub struct PlayerState {
     //Maybe this information should live in the public information?
     pub objectively_possible_cards_according_to_hints3: IndexMap<usize, CardSet>,
     pub touched: IndexSet<usize>,
+    /// Every card that can be in the game, what a card is before any hints.
+    all_cards: CardSet,
     interpretations_some_of_which_self_should_entertain: Vec<Interpretations>,
 }
 
 impl PlayerState {
-    pub fn new() -> Self {
+    pub fn new(all_cards: CardSet) -> Self {
         Self {
             cards: HandCards::new(),
             objectively_possible_cards_according_to_hints3: IndexMap::new(),
             touched: IndexSet::new(),
+            all_cards,
             interpretations_some_of_which_self_should_entertain: Vec::new(),
         }
     }
@@ -35,7 +38,7 @@ impl PlayerState {
         self.cards.add_card(id);
         let previous = self
             .objectively_possible_cards_according_to_hints3
-            .insert(id, CardSet::all());
+            .insert(id, self.all_cards);
         assert!(previous.is_none());
     }
 
@@ -43,9 +46,9 @@ impl PlayerState {
         let id = self.cards.play_or_discard_card(position);
         let removed = self
             .objectively_possible_cards_according_to_hints3
-            .remove(&id);
+            .swap_remove(&id);
         assert!(removed.is_some());
-        self.touched.remove(&id);
+        self.touched.swap_remove(&id);
     }
 
     pub fn chop_position(&self) -> Option<usize> {
@@ -69,7 +72,10 @@ impl PlayerState {
         assert_eq!(self.cards.current_hand_size, positions.hand_size);
 
         let touched_positions = self.touched_positions();
-        let focus_position = positions.focus_position(touched_positions);
+        //A null hint has no focus, so it can only be a stall.
+        let Some(focus_position) = positions.focus_position(touched_positions) else {
+            return Interpretations::new(vec![Interpretation::no_additional_info()]);
+        };
         let touches_no_new_cards = touched_positions.contains(focus_position);
 
         let potential_burned_clue = 2 <= giver_stall_severity && touches_no_new_cards;
@@ -98,7 +104,7 @@ impl PlayerState {
             if hinted_property == Property::Number(Number::Two)
                 || hinted_property == Property::Number(Number::Five)
             {
-                let special_saves = CardSet::with_property(hinted_property);
+                let special_saves = CardSet::with_property(hinted_property, &state.rules);
                 direct_interpretation_focus_possibilities.extend(special_saves)
             }
         }
@@ -138,10 +144,10 @@ impl PlayerState {
             let card_id = self.cards.cards[pos].unwrap();
             let possible = &mut self.objectively_possible_cards_according_to_hints3[&card_id];
             if positions.contains(pos) {
-                possible.apply(hinted_property);
+                possible.apply(hinted_property, &state.rules);
                 self.touched.insert(card_id);
             } else {
-                possible.apply_not(hinted_property);
+                possible.apply_not(hinted_property, &state.rules);
             }
         }
     }
@@ -281,7 +287,7 @@ impl PlayerState {
         touched_in_other_hands_or_more: &CardSet,
         cards_self_definitely_sees_all_copies_of: &CardSet,
     ) -> usize {
-        if state.clues == 8 {
+        if state.clues == state.rules.max_clue_count() {
             4
         } else if self.potentially_is_locked_with_no_known_playable_card(
             &state.firework,
@@ -0,0 +1,64 @@
+//! The hands as one seat sees them, for players built outside of this crate.
+
+use crate::{card::Card, state::Rules};
+
+use super::{action::Action, Property};
+
+/// The hands of every seat, newest card first, as witnessed by one seat whose own cards
+/// stay hidden. Has to be told about every draw and every action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VisibleHands {
+    rules: Rules,
+    seat: usize,
+    hands: Vec<Vec<Option<Card>>>,
+}
+
+impl VisibleHands {
+    pub fn new(rules: &Rules, seat: usize) -> Self {
+        Self {
+            rules: *rules,
+            seat,
+            hands: vec![Vec::new(); rules.number_of_players],
+        }
+    }
+
+    /// The seat whose cards are hidden.
+    pub fn seat(&self) -> usize {
+        self.seat
+    }
+
+    /// The hand of `seat`, with `None` for every hidden card. Empty for seats beyond the table.
+    pub fn hand(&self, seat: usize) -> &[Option<Card>] {
+        self.hands.get(seat).map_or(&[], Vec::as_slice)
+    }
+
+    /// The cards of another seat, `None` for the own seat and seats beyond the table.
+    pub fn cards(&self, seat: usize) -> Option<Vec<Card>> {
+        if seat == self.seat {
+            return None;
+        }
+        self.hands.get(seat)?.iter().copied().collect()
+    }
+
+    /// A hint at `receiver` about `hinted_property`, `None` if the cards of `receiver` are not
+    /// visible.
+    pub fn hint(&self, receiver: usize, hinted_property: Property) -> Option<Action> {
+        let cards = self.cards(receiver)?;
+        Some(Action::hint(receiver, hinted_property, &cards, &self.rules))
+    }
+
+    pub fn witness_draw(&mut self, player: usize, card: Option<Card>) {
+        let card = card.filter(|_| player != self.seat);
+        self.hands[player].insert(0, card);
+    }
+
+    /// Returns the played or discarded card if it was visible.
+    pub fn witness_action(&mut self, action: Action, player: usize) -> Option<Card> {
+        match action {
+            Action::Play { position, .. } | Action::Discard { position, .. } => {
+                self.hands[player].remove(position - 1)
+            }
+            Action::Hint { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Playing many games in parallel to benchmark players.
+
+use std::{
+    ops::Range,
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+    time::Duration,
+};
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+use crate::{
+    player::Player,
+    solver::{solve, Budget},
+    state::{deck::Deck, record_game, Rules},
+};
+
+/// The aggregated results of [`simulate`].
+/// Everything except `decision_time` is independent of the number of threads.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Statistics {
+    pub games: usize,
+    /// The number of won games for each score, lost games are not included.
+    pub score_histogram: Vec<usize>,
+    /// The games lost by striking out, in ascending order.
+    pub strikeouts: Vec<u64>,
+    /// The games aborted because a player requested an illegal action, in ascending order.
+    pub rule_violations: Vec<u64>,
+    pub decision_time: Duration,
+    pub decisions: usize,
+    /// The number of decks for each best score they allow, only filled by
+    /// [`simulate_with_ceilings`]. A ceiling is never below the score the players reached.
+    pub ceiling_histogram: Vec<usize>,
+    /// The games whose ceiling is only a lower bound, in ascending order.
+    pub unproven_ceilings: Vec<u64>,
+    /// The games in which the players reached the ceiling of their deck.
+    pub games_at_ceiling: usize,
+}
+
+impl Statistics {
+    fn new(rules: &Rules) -> Self {
+        Self {
+            games: 0,
+            score_histogram: vec![0; rules.max_score() + 1],
+            strikeouts: Vec::new(),
+            rule_violations: Vec::new(),
+            decision_time: Duration::ZERO,
+            decisions: 0,
+            ceiling_histogram: vec![0; rules.max_score() + 1],
+            unproven_ceilings: Vec::new(),
+            games_at_ceiling: 0,
+        }
+    }
+
+    fn merge(&mut self, other: Statistics) {
+        self.games += other.games;
+        for (number, other_number) in self.score_histogram.iter_mut().zip(other.score_histogram) {
+            *number += other_number;
+        }
+        self.strikeouts.extend(other.strikeouts);
+        self.rule_violations.extend(other.rule_violations);
+        self.decision_time += other.decision_time;
+        self.decisions += other.decisions;
+        for (number, other_number) in self
+            .ceiling_histogram
+            .iter_mut()
+            .zip(other.ceiling_histogram)
+        {
+            *number += other_number;
+        }
+        self.unproven_ceilings.extend(other.unproven_ceilings);
+        self.games_at_ceiling += other.games_at_ceiling;
+    }
+
+    /// `sum` divided by the number of games, `None` without games.
+    fn per_game(&self, sum: usize) -> Option<f64> {
+        (self.games > 0).then(|| sum as f64 / self.games as f64)
+    }
+
+    /// The average score, counting lost and aborted games as 0. `None` without games.
+    pub fn mean_score(&self) -> Option<f64> {
+        self.per_game((0..).zip(&self.score_histogram).map(|(s, n)| s * n).sum())
+    }
+
+    /// The average best score of the decks, see [`simulate_with_ceilings`]. `None` without games.
+    pub fn mean_ceiling(&self) -> Option<f64> {
+        self.per_game((0..).zip(&self.ceiling_histogram).map(|(s, n)| s * n).sum())
+    }
+
+    /// The share of the points the decks allowed that the players scored. `None` without games.
+    pub fn score_relative_to_ceiling(&self) -> Option<f64> {
+        Some(self.mean_score()? / self.mean_ceiling()?)
+    }
+
+    /// The share of games lost by striking out. `None` without games.
+    pub fn strikeout_rate(&self) -> Option<f64> {
+        self.per_game(self.strikeouts.len())
+    }
+
+    pub fn time_per_decision(&self) -> Duration {
+        self.decision_time / self.decisions.max(1).try_into().unwrap()
+    }
+}
+
+/// The deck of `game` in a simulation with `base_seed`.
+/// Every game draws from its own stream, so it does not depend on the other games.
+pub fn deck_for_game(rules: &Rules, base_seed: u64, game: u64) -> Deck {
+    let mut rng = ChaCha20Rng::seed_from_u64(base_seed);
+    rng.set_stream(game);
+    rules.get_shuffled_deck(&mut rng)
+}
+
+/// Plays every game in `games` on `threads` threads, with players created by `players`.
+pub fn simulate<F>(
+    rules: Rules,
+    players: F,
+    base_seed: u64,
+    games: Range<u64>,
+    threads: usize,
+) -> Statistics
+where
+    F: Fn(Rules) -> Vec<Box<dyn Player>> + Sync,
+{
+    run(rules, players, base_seed, games, threads, None)
+}
+
+/// Like [`simulate`], but also solves every deck with `budget` to compare the scores with the
+/// best score the deck allows. Only independent of the number of threads without a time budget.
+pub fn simulate_with_ceilings<F>(
+    rules: Rules,
+    players: F,
+    base_seed: u64,
+    games: Range<u64>,
+    threads: usize,
+    budget: Budget,
+) -> Statistics
+where
+    F: Fn(Rules) -> Vec<Box<dyn Player>> + Sync,
+{
+    run(rules, players, base_seed, games, threads, Some(budget))
+}
+
+fn run<F>(
+    rules: Rules,
+    players: F,
+    base_seed: u64,
+    games: Range<u64>,
+    threads: usize,
+    budget: Option<Budget>,
+) -> Statistics
+where
+    F: Fn(Rules) -> Vec<Box<dyn Player>> + Sync,
+{
+    assert!(threads > 0);
+
+    let next_game = AtomicU64::new(games.start);
+
+    let mut statistics = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut statistics = Statistics::new(&rules);
+
+                    loop {
+                        let game = next_game.fetch_add(1, Ordering::Relaxed);
+                        if game >= games.end {
+                            return statistics;
+                        }
+
+                        let deck = deck_for_game(&rules, base_seed, game);
+                        statistics.games += 1;
+
+                        let score = match record_game(rules, deck.clone(), players(rules)) {
+                            Ok((score, _, (decision_time, decisions))) => {
+                                match score {
+                                    Some(score) => statistics.score_histogram[score] += 1,
+                                    None => statistics.strikeouts.push(game),
+                                }
+                                statistics.decision_time += decision_time;
+                                statistics.decisions += decisions;
+                                score.unwrap_or(0)
+                            }
+                            Err(_) => {
+                                statistics.rule_violations.push(game);
+                                0
+                            }
+                        };
+
+                        if let Some(budget) = budget {
+                            let solution = solve(rules, deck, budget).expect("the deck is dealt");
+                            let ceiling = solution.score.max(score);
+                            if !solution.is_optimal && ceiling < rules.max_score() {
+                                statistics.unproven_ceilings.push(game);
+                            }
+                            statistics.ceiling_histogram[ceiling] += 1;
+                            if score == ceiling {
+                                statistics.games_at_ceiling += 1;
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .fold(Statistics::new(&rules), |mut acc, statistics| {
+                acc.merge(statistics);
+                acc
+            })
+    });
+
+    statistics.strikeouts.sort_unstable();
+    statistics.rule_violations.sort_unstable();
+    statistics.unproven_ceilings.sort_unstable();
+
+    statistics
+}
@@ -0,0 +1,212 @@
+//! A perfect-information search for the best score a deck allows.
+
+use std::time::{Duration, Instant};
+
+use crate::{
+    card::card_set::CardSet,
+    player::action::Action,
+    state::{deck::Deck, Rules, State},
+};
+
+/// When [`solve`] stops searching for a better score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Budget {
+    /// The number of visited states.
+    pub nodes: usize,
+    /// Makes the result depend on the speed of the machine.
+    pub time: Option<Duration>,
+}
+
+impl Budget {
+    pub fn new(nodes: usize) -> Self {
+        Self { nodes, time: None }
+    }
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Self::new(100_000)
+    }
+}
+
+/// The result of [`solve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    /// The best score found, lost games count as 0.
+    pub score: usize,
+    /// The actions of a game that reaches `score`, as requested by the players.
+    pub actions: Vec<Action>,
+    /// Whether no better score is possible. Otherwise `score` is only a lower bound,
+    /// because the budget ran out.
+    pub is_optimal: bool,
+    pub nodes: usize,
+}
+
+/// Searches the best score for `deck` when every player sees every card, `None` if the deck is
+/// too small to deal.
+///
+/// The search is depth first, starting with plays, and skips actions that cannot be better than
+/// another one: of several copies of a card only one is played or discarded, of the cards that
+/// can never be played only one is discarded, and only one hint is given because hints carry no
+/// information. Branches that cannot beat the best score so far are cut off.
+/// The budget is only checked after the first game is complete, so there is always a solution.
+pub fn solve(rules: Rules, deck: Deck, budget: Budget) -> Option<Solution> {
+    let state = State::dealt(&rules, deck)?;
+
+    let mut search = Search {
+        rules,
+        budget,
+        start: Instant::now(),
+        nodes: 0,
+        is_exhausted: false,
+        upper_bound: upper_bound(&state),
+        best: None,
+        actions: Vec::new(),
+    };
+    search.search(&state);
+
+    let (score, actions) = search.best.expect("the first game is always completed");
+    Some(Solution {
+        score,
+        actions,
+        is_optimal: !search.is_exhausted || score == search.upper_bound,
+        nodes: search.nodes,
+    })
+}
+
+/// The score cannot exceed what is still reachable, and every play needs its own turn.
+fn upper_bound(state: &State) -> usize {
+    let score = state.firework().score();
+    score + (state.max_reachable_score() - score).min(state.remaining_plays())
+}
+
+struct Search {
+    rules: Rules,
+    budget: Budget,
+    start: Instant,
+    nodes: usize,
+    is_exhausted: bool,
+    /// The bound of the dealt state, reaching it ends the search.
+    upper_bound: usize,
+    best: Option<(usize, Vec<Action>)>,
+    actions: Vec<Action>,
+}
+
+impl Search {
+    fn search(&mut self, state: &State) {
+        if let Some((best, _)) = self.best {
+            if best == self.upper_bound || self.is_exhausted {
+                return;
+            }
+            if self.nodes >= self.budget.nodes
+                || self
+                    .budget
+                    .time
+                    .is_some_and(|time| self.start.elapsed() >= time)
+            {
+                self.is_exhausted = true;
+                return;
+            }
+            if upper_bound(state) <= best {
+                return;
+            }
+        }
+        self.nodes += 1;
+
+        if let Some(outcome) = state.is_concluded() {
+            let score = outcome.unwrap_or(0);
+            if self.best.as_ref().is_none_or(|&(best, _)| score > best) {
+                self.best = Some((score, self.actions.clone()));
+            }
+            return;
+        }
+
+        for action in self.candidates(state) {
+            let mut next = state.clone();
+            next.apply_action(action)
+                .expect("candidates are legal actions");
+            next.go_to_next_player();
+
+            self.actions.push(action);
+            self.search(&next);
+            self.actions.pop();
+        }
+    }
+
+    /// The legal actions of the active player that are worth trying, most promising first.
+    /// Never empty, so that every game is completed.
+    fn candidates(&self, state: &State) -> Vec<Action> {
+        let seat = state.active_seat();
+        let hand: Vec<_> = state.hand(seat).cards().collect();
+
+        let mut trash = state.firework().already_played();
+        trash.merge(&state.discard_pile().unreachable(&self.rules));
+        // Keeping a card that is still needed is never worse than keeping trash, so with trash
+        // in hand only trash is discarded or misplayed.
+        let has_trash = hand.iter().any(|&card| trash.contains(card));
+
+        let mut plays = Vec::new();
+        let mut trash_discards = Vec::new();
+        let mut hints = Vec::new();
+        let mut discards = Vec::new();
+        let mut misplays = Vec::new();
+        let mut played = CardSet::none();
+        let mut discarded = CardSet::none();
+        let mut misplayed = CardSet::none();
+
+        for action in state.legal_actions(seat) {
+            match action {
+                Action::Play { position, .. } => {
+                    let card = hand[position - 1];
+                    if state.firework().is_playable(card) {
+                        if played.add(card) {
+                            plays.push(action);
+                        }
+                    } else if state.strikes() + 1 < self.rules.max_strikes {
+                        if trash.contains(card) {
+                            if misplays.is_empty() {
+                                misplays.push(action);
+                            }
+                        } else if !has_trash && misplayed.add(card) {
+                            misplays.push(action);
+                        }
+                    }
+                }
+                Action::Discard { position, .. } => {
+                    let card = hand[position - 1];
+                    if trash.contains(card) {
+                        if trash_discards.is_empty() {
+                            trash_discards.push(action);
+                        }
+                    } else if !has_trash && discarded.add(card) {
+                        discards.push((action, card));
+                    }
+                }
+                Action::Hint { .. } => {
+                    if hints.is_empty() {
+                        hints.push(action);
+                    }
+                }
+            }
+        }
+
+        // Losing a card with many copies left is least likely to cost points.
+        discards.sort_by_key(|&(_, card)| {
+            state.discard_pile().count(card) as isize - self.rules.copies(card) as isize
+        });
+
+        let candidates: Vec<_> = plays
+            .into_iter()
+            .chain(trash_discards)
+            .chain(hints)
+            .chain(discards.into_iter().map(|(action, _)| action))
+            .chain(misplays)
+            .collect();
+        // Only misplays that lose the game are left, one of them has to be tried.
+        if candidates.is_empty() {
+            return state.legal_actions(seat).into_iter().take(1).collect();
+        }
+        candidates
+    }
+}
@@ -1,7 +1,4 @@
-use std::{
-    fmt::Display,
-    time::{Duration, Instant},
-};
+use std::{fmt::Display, time::Duration};
 
 use colored::Colorize;
 use indexmap::IndexMap;
@@ -10,23 +7,48 @@ use rand_chacha::ChaCha20Rng;
 
 use crate::{
     card::{card_set::CardSet, Card, Color, Number},
-    player::{action::Action, basic::BasicPlayer, Player, Property},
+    player::{action::Action, basic::BasicPlayer, player_name, Player, PositionSet, Property},
 };
 
-use self::{card_pile::CardPile, deck::Deck};
+use self::{
+    card_pile::CardPile,
+    clue_count::ClueCount,
+    deck::Deck,
+    game::{Game, TurnOutcome},
+    record::Record,
+    suit::Suits,
+};
 
-mod card_pile;
+pub mod card_pile;
+pub mod clue_count;
+pub mod game;
+pub mod record;
+pub mod suit;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PublicState {
     pub firework: Firework,
     pub discard_pile: CardPile,
     pub rules: Rules,
-    pub clues: usize,
+    pub clues: ClueCount,
     pub strikes: usize,
+    /// The number of cards left to draw.
+    pub deck_size: usize,
+    /// The current turn, starting at 1.
+    pub turn: usize,
+    /// How many actions were taken since the last card was drawn.
+    pub actions_with_empty_deck: usize,
+    pub hints_given: usize,
 }
 impl PublicState {
+    /// A play without its card, as with hidden plays, reveals nothing.
     pub fn apply_action(&mut self, action: Action) {
+        self.turn += 1;
+        if self.deck_size == 0 {
+            self.actions_with_empty_deck += 1;
+        }
+
         match action {
             Action::Play {
                 card: Some(card), ..
@@ -42,37 +64,20 @@ impl PublicState {
         }
     }
 
+    /// The cards that are not playable yet and have only one copy left.
+    /// Fives are left out, they are saved with a five hint instead.
     pub(crate) fn critical_saves(&self) -> CardSet {
-        let mut result = CardSet::none();
-        for (&color, &maybe_number) in &self.firework.piles {
-            let two = Card {
-                color,
-                number: Number::Two,
-            };
-            if self.discard_pile.contains(two) && maybe_number.is_none() {
-                result.add(two);
-            }
-
-            let three = Card {
-                color,
-                number: Number::Three,
-            };
-            if self.discard_pile.contains(three)
-                && (maybe_number.is_none() || maybe_number == Some(Number::One))
-            {
-                result.add(three);
-            }
-
-            let four = Card {
-                color,
-                number: Number::Four,
-            };
-            if self.discard_pile.contains(four)
-                && (maybe_number.is_none()
-                    || maybe_number == Some(Number::One)
-                    || maybe_number == Some(Number::Two))
-            {
-                result.add(four);
+        let mut result = CardSet::all_in(&self.rules);
+        result.exclude(&CardSet::with_property(
+            Property::Number(Number::Five),
+            &self.rules,
+        ));
+        result.exclude(&self.firework.already_played());
+        result.exclude(&self.firework.currently_playable());
+
+        for card in result.iter() {
+            if self.discard_pile.count(card) + 1 < self.rules.copies(card) {
+                result.remove(card);
             }
         }
 
@@ -80,9 +85,10 @@ impl PublicState {
     }
 
     fn add_clue(&mut self) -> bool {
-        let succ = self.clues < self.rules.max_clues;
+        let max_clues = self.rules.max_clue_count();
+        let succ = self.clues < max_clues;
         if succ {
-            self.clues += 1
+            self.clues = max_clues.min(self.clues + self.rules.clue_recovery());
         }
         succ
     }
@@ -99,23 +105,71 @@ impl PublicState {
         if !succ {
             self.strikes += 1;
             self.discard_pile.add(&card);
-        } else if card.number == Number::Five {
+        } else if self.firework.is_pile_complete(card.color) && !self.rules.hidden_plays {
             self.add_clue();
         }
     }
 
     pub(crate) fn hint(&mut self) {
-        assert_ne!(self.clues, 0);
-        self.clues -= 1;
+        assert!(self.clues.allows_hint());
+        self.clues = self.clues - ClueCount::ONE;
+        self.hints_given += 1;
+    }
+
+    /// Some player drew a card, including the cards dealt at the start.
+    pub fn witness_draw(&mut self) {
+        assert_ne!(self.deck_size, 0);
+        self.deck_size -= 1;
+    }
+
+    /// Whether the last card was drawn, so that every player only has a few turns left.
+    pub fn is_final_round(&self) -> bool {
+        self.deck_size == 0
     }
 
-    pub(crate) fn new(rules: Rules) -> Self {
+    /// How many more cards can be discarded, while every player still gets a turn to play the
+    /// missing cards after the deck is empty. Negative if [`PublicState::max_score`] cannot be
+    /// reached anymore.
+    pub fn pace(&self) -> isize {
+        (self.firework.score() + self.deck_size + self.rules.number_of_players) as isize
+            - self.max_score() as isize
+    }
+
+    /// The best score that can still be reached with the cards that are not discarded.
+    pub fn max_score(&self) -> usize {
+        self.firework
+            .max_reachable_score(&self.discard_pile, &self.rules)
+    }
+
+    /// The best score that can still be reached on the pile of `color`.
+    pub fn max_score_of(&self, color: Color) -> usize {
+        self.firework
+            .max_reachable_score_of(color, &self.discard_pile, &self.rules)
+    }
+
+    /// The cards gotten per hint given, counting only played cards because touched cards
+    /// are not public. `None` before the first hint.
+    pub fn efficiency(&self) -> Option<f64> {
+        (self.hints_given > 0).then(|| self.firework.score() as f64 / self.hints_given as f64)
+    }
+
+    /// The state before dealing a complete deck, see [`PublicState::with_deck_size`].
+    pub fn new(rules: Rules) -> Self {
+        Self::with_deck_size(rules, rules.all_cards().len())
+    }
+
+    /// The state before dealing a deck of `deck_size` cards, which may be shorter than usual.
+    pub fn with_deck_size(rules: Rules, deck_size: usize) -> Self {
         Self {
-            firework: Firework::new(&rules.used_colors()),
+            firework: Firework::for_rules(&rules),
             discard_pile: CardPile::new(),
             rules,
-            clues: rules.max_clues,
+            clues: rules.max_clue_count(),
             strikes: 0,
+            deck_size,
+            turn: 1,
+            actions_with_empty_deck: 0,
+            hints_given: 0,
         }
     }
 
@@ -126,20 +180,22 @@ impl PublicState {
     }
 }
 
-struct State {
+/// The complete state of a game as seen by the engine, including all hands and the deck.
+#[derive(Clone)]
+pub struct State {
     deck: Deck,
     active_player_id: usize,
-    remaining_hints: usize,
+    remaining_hints: ClueCount,
     strikes: usize,
     firework: Firework,
-    number_of_players: usize,
+    rules: Rules,
     number_of_actions_with_empty_deck: usize,
     hands: Vec<Hand>,
     discard: CardPile,
 }
 
 #[derive(Clone)]
-struct Hand {
+pub struct Hand {
     clued_cards: Vec<(Card, Vec<Property>, Vec<Property>)>,
     max_size: usize,
 }
@@ -156,54 +212,99 @@ impl Hand {
         self.clued_cards.insert(0, (card, Vec::new(), Vec::new()))
     }
 
-    fn remove(&mut self, position: usize) -> Result<Card, RuleViolation> {
-        let internal_index = position
-            .checked_sub(1)
-            .ok_or(RuleViolation::InvalidCardPosition)?;
-        if self.clued_cards.len() <= internal_index {
-            return Err(RuleViolation::InvalidCardPosition);
-        }
-        Ok(self.clued_cards.remove(internal_index).0)
+    pub fn len(&self) -> usize {
+        self.clued_cards.len()
     }
 
-    fn give_hint(
-        &mut self,
-        hinted_property: Property,
-        positions: crate::player::PositionSet,
-    ) -> Result<(), RuleViolation> {
-        for (ii, (card, pos, neg)) in self.clued_cards.iter_mut().enumerate() {
-            let i = ii + 1;
+    pub fn is_empty(&self) -> bool {
+        self.clued_cards.is_empty()
+    }
 
-            let satisfied = card.satisfies(hinted_property);
+    /// The cards from position 1 (newest) onwards.
+    pub fn cards(&self) -> impl Iterator<Item = Card> + '_ {
+        self.clued_cards.iter().map(|&(card, _, _)| card)
+    }
 
-            if satisfied != positions.contains(i) {
-                Err(RuleViolation::IncorrectHint)?;
-            }
+    /// The hinted properties the card at `position` satisfies, and the ones it doesn't.
+    /// `None` if there is no card at `position`.
+    pub fn hints(&self, position: usize) -> Option<(&[Property], &[Property])> {
+        let (_, pos, neg) = self.clued_cards.get(position.checked_sub(1)?)?;
+        Some((pos, neg))
+    }
 
-            if satisfied {
+    fn remove(&mut self, position: usize) -> Card {
+        self.clued_cards.remove(position - 1).0
+    }
+
+    fn give_hint(&mut self, hinted_property: Property, positions: PositionSet) {
+        for (ii, (_, pos, neg)) in self.clued_cards.iter_mut().enumerate() {
+            if positions.contains(ii + 1) {
                 pos.push(hinted_property);
             } else {
                 neg.push(hinted_property);
             }
         }
-
-        Ok(())
     }
 }
 
-impl Display for Hand {
+/// What [`State::display`] and [`Hand::display`] show besides the cards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DisplayOptions {
+    /// The point of view: the cards of this seat are shown as `??`.
+    pub hidden_seat: Option<usize>,
+    /// Whether every hand starts with the name of its seat.
+    pub names: bool,
+    /// Whether the hinted properties are listed after every card, `+` for the ones it
+    /// satisfies and `-` for the others.
+    pub hints: bool,
+}
+
+/// A [`Hand`] shown with [`DisplayOptions`].
+pub struct HandDisplay<'a> {
+    hand: &'a Hand,
+    is_hidden: bool,
+    hints: bool,
+}
+
+impl Display for HandDisplay<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for ii in 0..self.max_size {
-            if let Some((card, pos, _)) = self.clued_cards.get(ii) {
-                let card_string = format!("{card}");
+        fn properties(properties: &[Property]) -> String {
+            properties.iter().map(|p| p.to_string()).collect()
+        }
 
-                if pos.is_empty() {
-                    write!(f, "{} ", card_string)?;
-                } else {
-                    write!(f, "{} ", card_string.underline())?;
+        for ii in 0..self.hand.max_size {
+            let Some((card, pos, neg)) = self.hand.clued_cards.get(ii) else {
+                write!(f, "   ")?;
+                if self.hints {
+                    write!(f, "{:10} ", "")?;
                 }
+                continue;
+            };
+
+            let card_string = if self.is_hidden {
+                "??".to_string()
             } else {
-                write!(f, "   ")?;
+                format!("{card}")
+            };
+            if pos.is_empty() {
+                write!(f, "{} ", card_string)?;
+            } else {
+                write!(f, "{} ", card_string.underline())?;
+            }
+
+            if self.hints {
+                let mut clues = String::new();
+                if !pos.is_empty() {
+                    clues = format!("+{}", properties(pos));
+                }
+                if !neg.is_empty() {
+                    if !clues.is_empty() {
+                        clues.push(' ');
+                    }
+                    clues.push('-');
+                    clues.push_str(&properties(neg));
+                }
+                write!(f, "{clues:<10} ")?;
             }
         }
 
@@ -211,99 +312,315 @@ impl Display for Hand {
     }
 }
 
-impl Display for State {
+impl Hand {
+    /// Shows the hand with its cards as `??` if `is_hidden`, and with its hints if `hints`.
+    pub fn display(&self, is_hidden: bool, hints: bool) -> HandDisplay<'_> {
+        HandDisplay {
+            hand: self,
+            is_hidden,
+            hints,
+        }
+    }
+}
+
+impl Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display(false, false))
+    }
+}
+
+/// A [`State`] shown with [`DisplayOptions`].
+pub struct StateDisplay<'a> {
+    state: &'a State,
+    options: DisplayOptions,
+    seen_in: Option<&'a PublicState>,
+}
+
+impl<'a> StateDisplay<'a> {
+    /// Shows the firework, strikes and discards of `public_state` instead, for a point of view
+    /// that does not know every played card.
+    pub fn as_seen_in(self, public_state: &'a PublicState) -> Self {
+        Self {
+            seen_in: Some(public_state),
+            ..self
+        }
+    }
+}
+
+impl Display for StateDisplay<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        fn print_player(
-            state: &State,
-            id: usize,
-            f: &mut std::fmt::Formatter<'_>,
-        ) -> std::fmt::Result {
-            let prefix = if id == state.active_player_id {
-                '>'
+        let state = self.state;
+        let options = self.options;
+        let (firework, strikes, discard) = match self.seen_in {
+            Some(seen) => (&seen.firework, seen.strikes, &seen.discard_pile),
+            None => (&state.firework, state.strikes, &state.discard),
+        };
+
+        // The first three rows also show firework, clues and discards, so with less than three
+        // players, we pad with empty rows.
+        for id in 0..state.rules.number_of_players.max(3) {
+            let name = if options.names && id < state.rules.number_of_players {
+                format!("{:<7} ", player_name(id))
+            } else if options.names {
+                format!("{:8}", "")
             } else {
-                ' '
+                String::new()
             };
-            write!(f, "{} {}", prefix, state.hands[id])
-        }
 
-        print_player(self, 0, f)?;
-        writeln!(f, "    {}", self.firework)?;
-        print_player(self, 1, f)?;
-        writeln!(f, "    {}     {}", self.remaining_hints, self.strikes)?;
-        print_player(self, 2, f)?;
-        writeln!(f, "    {}", self.discard)?;
-        for i in 3..self.number_of_players {
-            print_player(self, i, f)?;
-            writeln!(f)?;
+            if id < state.rules.number_of_players {
+                let prefix = if id == state.active_player_id {
+                    '>'
+                } else {
+                    ' '
+                };
+                let hand = state.hands[id].display(options.hidden_seat == Some(id), options.hints);
+                write!(f, "{} {}{}", prefix, name, hand)?;
+            } else {
+                let empty = Hand::new(state.hands[0].max_size);
+                write!(f, "  {}{}", name, empty.display(false, options.hints))?;
+            }
+
+            match id {
+                0 => writeln!(f, "    {}", firework)?,
+                1 => writeln!(f, "    {}     {}", state.remaining_hints, strikes)?,
+                2 => writeln!(f, "    {}", discard)?,
+                _ => writeln!(f)?,
+            }
         }
 
         Ok(())
     }
 }
 
+impl State {
+    /// Shows the state as seen by `options.hidden_seat`, or with all cards.
+    pub fn display(&self, options: DisplayOptions) -> StateDisplay<'_> {
+        StateDisplay {
+            state: self,
+            options,
+            seen_in: None,
+        }
+    }
+}
+
+impl Display for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display(DisplayOptions::default()))
+    }
+}
+
 impl State {
     fn new(rules: &Rules, deck: Deck) -> Self {
-        let firework = Firework::new(&rules.used_colors());
-        let number_of_players = rules.number_of_players;
+        let firework = Firework::for_rules(rules);
 
         Self {
             deck,
             active_player_id: 0,
-            remaining_hints: rules.max_clues,
+            remaining_hints: rules.max_clue_count(),
             strikes: 0,
             firework,
-            number_of_players,
+            rules: *rules,
             number_of_actions_with_empty_deck: 0,
-            hands: vec![Hand::new(rules.hand_size); number_of_players],
+            hands: vec![Hand::new(rules.hand_size); rules.number_of_players],
             discard: CardPile::new(),
         }
     }
 
+    pub fn active_seat(&self) -> usize {
+        self.active_player_id
+    }
+
+    pub fn number_of_players(&self) -> usize {
+        self.rules.number_of_players
+    }
+
+    pub fn hand(&self, seat: usize) -> &Hand {
+        &self.hands[seat]
+    }
+
+    pub fn firework(&self) -> &Firework {
+        &self.firework
+    }
+
+    pub fn discard_pile(&self) -> &CardPile {
+        &self.discard
+    }
+
+    pub fn clues(&self) -> ClueCount {
+        self.remaining_hints
+    }
+
+    pub fn strikes(&self) -> usize {
+        self.strikes
+    }
+
+    pub fn deck(&self) -> &Deck {
+        &self.deck
+    }
+
     fn draw(&mut self) -> Option<Card> {
         let new = self.deck.draw()?;
         self.hands[self.active_player_id].draw(new);
         Some(new)
     }
 
-    fn is_concluded(&self) -> Option<Option<usize>> {
-        if self.strikes == 3 {
-            Some(None)
-        } else if self.number_of_actions_with_empty_deck == self.number_of_players
+    /// The state after dealing the starting hands, `None` if the deck is too small.
+    pub(crate) fn dealt(rules: &Rules, deck: Deck) -> Option<State> {
+        let mut state = State::new(rules, deck);
+
+        for _ in 0..rules.number_of_players {
+            for _ in 0..rules.hand_size {
+                state.draw()?;
+            }
+            state.go_to_next_player();
+        }
+
+        Some(state)
+    }
+
+    pub fn is_concluded(&self) -> Option<Option<usize>> {
+        let score = self.firework.score();
+
+        if self.strikes >= self.rules.max_strikes {
+            match self.rules.strikeout_scoring {
+                StrikeoutScoring::Zero => Some(None),
+                StrikeoutScoring::KeepScore => Some(Some(score)),
+            }
+        } else if (self.deck.is_empty()
+            && self.number_of_actions_with_empty_deck
+                == self.rules.number_of_players * self.rules.final_round_turns_per_player)
             || self.firework.is_complete()
+            || (self.rules.end_at_max_reachable_score && score == self.max_reachable_score())
         {
-            Some(Some(self.firework.score()))
+            Some(Some(score))
         } else {
             None
         }
     }
 
-    fn remove_card(&mut self, position: usize) -> Result<Card, RuleViolation> {
-        self.hands[self.active_player_id].remove(position)
+    /// The score if every card that is not yet discarded was played.
+    pub fn max_reachable_score(&self) -> usize {
+        self.firework
+            .max_reachable_score(&self.discard, &self.rules)
+    }
+
+    /// An upper bound for the number of cards that can still be played: every play draws a card
+    /// until the deck is empty, after that only the final round is left.
+    pub(crate) fn remaining_plays(&self) -> usize {
+        self.deck.len() + self.rules.number_of_players * self.rules.final_round_turns_per_player
+            - self.number_of_actions_with_empty_deck
+    }
+
+    /// Whether `action` would be accepted if it was the turn of `seat`.
+    fn validate_for(&self, seat: usize, action: Action) -> Result<(), RuleViolation> {
+        let hand = &self.hands[seat];
+
+        match action {
+            Action::Play { card, position } | Action::Discard { card, position } => {
+                if card.is_some() {
+                    Err(RuleViolation::InvalidCardInformation)?;
+                }
+
+                if matches!(action, Action::Discard { .. })
+                    && self.remaining_hints == self.rules.max_clue_count()
+                {
+                    Err(RuleViolation::NoMoreDiscardsAvailable)?;
+                }
+
+                if !(1..=hand.len()).contains(&position) {
+                    Err(RuleViolation::InvalidCardPosition)?;
+                }
+            }
+            Action::Hint {
+                receiver,
+                hinted_property,
+                positions,
+            } => {
+                if receiver == seat || self.rules.number_of_players <= receiver {
+                    Err(RuleViolation::InvalidReceiver)?;
+                }
+
+                if !self.remaining_hints.allows_hint() {
+                    Err(RuleViolation::NoMoreHintsAvailable)?;
+                }
+
+                if !self.rules.allows(hinted_property) {
+                    Err(RuleViolation::DisallowedClue)?;
+                }
+
+                if !Property::all(&self.rules).contains(&hinted_property) {
+                    Err(RuleViolation::InvalidProperty)?;
+                }
+
+                if positions.is_empty() && !self.rules.allow_null_hints {
+                    Err(RuleViolation::NullHint)?;
+                }
+
+                let cards: Vec<Card> = self.hands[receiver].cards().collect();
+                if positions != PositionSet::matching(hinted_property, &cards, &self.rules) {
+                    Err(RuleViolation::IncorrectHint)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks `action` for the active player without changing the state.
+    pub fn validate(&self, action: Action) -> Result<(), RuleViolation> {
+        self.validate_for(self.active_player_id, action)
+    }
+
+    /// Every action that would be accepted if it was the turn of `seat`.
+    /// Does not check whether the game is already over.
+    pub fn legal_actions(&self, seat: usize) -> Vec<Action> {
+        let hand_size = self.hands[seat].len();
+        let mut result = Vec::new();
+
+        for position in 1..=hand_size {
+            result.push(Action::Play {
+                card: None,
+                position,
+            });
+        }
+
+        for position in 1..=hand_size {
+            result.push(Action::Discard {
+                card: None,
+                position,
+            });
+        }
+
+        for offset in 1..self.rules.number_of_players {
+            let receiver = (seat + offset) % self.rules.number_of_players;
+            let cards: Vec<Card> = self.hands[receiver].cards().collect();
+            for hinted_property in Property::all(&self.rules) {
+                result.push(Action::hint(receiver, hinted_property, &cards, &self.rules));
+            }
+        }
+
+        result.retain(|&action| self.validate_for(seat, action).is_ok());
+        result
     }
 
-    fn apply_action(
+    /// Leaves the state untouched if the action is rejected.
+    pub(crate) fn apply_action(
         &mut self,
         action: Action,
-        rules: &Rules,
     ) -> Result<(Option<Card>, Option<Card>), RuleViolation> {
+        self.validate(action)?;
+
         if self.deck.is_empty() {
             self.number_of_actions_with_empty_deck += 1;
         }
 
         match action {
-            Action::Play {
-                position,
-                card: no_card,
-            } => {
-                if no_card.is_some() {
-                    Err(RuleViolation::InvalidCardInformation)?;
-                }
-
-                let card = self.remove_card(position)?;
+            Action::Play { position, .. } => {
+                let card = self.hands[self.active_player_id].remove(position);
 
                 if self.firework.add(card) {
-                    if card.number == Number::Five && self.remaining_hints < rules.max_clues {
-                        self.remaining_hints += 1;
+                    if self.firework.is_pile_complete(card.color) && !self.rules.hidden_plays {
+                        self.recover_clue();
                     }
                 } else {
                     self.strikes += 1;
@@ -312,69 +629,99 @@ impl State {
 
                 Ok((Some(card), self.draw()))
             }
-            Action::Discard {
-                card: no_card,
-                position,
-            } => {
-                if no_card.is_some() {
-                    Err(RuleViolation::InvalidCardInformation)?;
-                }
-
-                if self.remaining_hints == rules.max_clues {
-                    Err(RuleViolation::NoMoreDiscardsAvailable)?;
-                }
-
-                let card = self.remove_card(position)?;
+            Action::Discard { position, .. } => {
+                let card = self.hands[self.active_player_id].remove(position);
                 self.discard.add(&card);
 
-                self.remaining_hints += 1;
+                self.recover_clue();
 
                 Ok((Some(card), self.draw()))
             }
-
             Action::Hint {
                 receiver,
                 hinted_property,
                 positions,
             } => {
-                if receiver == self.active_player_id || self.number_of_players <= receiver {
-                    Err(RuleViolation::InvalidReceiver)?;
-                }
-
-                if self.remaining_hints == 0 {
-                    Err(RuleViolation::NoMoreHintsAvailable)?;
-                }
+                self.hands[receiver].give_hint(hinted_property, positions);
 
-                if positions.is_empty() {
-                    Err(RuleViolation::NullHint)?;
-                }
-
-                self.hands[receiver].give_hint(hinted_property, positions)?;
-
-                self.remaining_hints -= 1;
+                self.remaining_hints = self.remaining_hints - ClueCount::ONE;
                 Ok((None, None))
             }
         }
     }
 
-    fn go_to_next_player(&mut self) {
+    fn recover_clue(&mut self) {
+        self.remaining_hints = self
+            .rules
+            .max_clue_count()
+            .min(self.remaining_hints + self.rules.clue_recovery());
+    }
+
+    pub(crate) fn go_to_next_player(&mut self) {
         self.active_player_id += 1;
-        self.active_player_id %= self.number_of_players;
+        self.active_player_id %= self.rules.number_of_players;
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+/// Which kinds of hints may be given.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AllowedClues {
+    All,
+    NumbersOnly,
+    ColorsOnly,
+}
+
+/// What a game that ends by reaching the strike limit scores.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StrikeoutScoring {
+    /// The game is lost.
+    Zero,
+    /// The game ends with the score of the firework.
+    KeepScore,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rules {
     pub number_of_players: usize,
     pub hand_size: usize,
     pub max_clues: usize,
+    /// The game ends as soon as this many strikes are reached.
+    pub max_strikes: usize,
+    pub strikeout_scoring: StrikeoutScoring,
+    /// After the last card is drawn, every player gets this many more turns.
+    pub final_round_turns_per_player: usize,
+    /// 5 or 6, the sixth suit is teal.
+    pub number_of_suits: usize,
+    /// Whether the rainbow suit, which is touched by every color hint, replaces the last suit
+    /// that is not dark.
+    pub rainbow: bool,
+    /// Whether the dark suit, with a single copy of every card, replaces the last suit.
+    pub dark: bool,
+    /// Whether the colorless suit, which no color hint touches, replaces the last suit
+    /// that is neither dark nor rainbow.
+    pub colorless: bool,
+    pub allowed_clues: AllowedClues,
+    /// Whether every suit can be built from one up or from five down, and has a start card
+    /// that can begin a pile in either direction.
+    pub up_or_down: bool,
+    /// Throw it in a hole: players are not told which card was played and whether the play
+    /// succeeded, so strikes stay hidden and completing a pile gives no clue.
+    pub hidden_plays: bool,
+    /// Whether discards and completed piles give back only half a clue.
+    pub clue_starved: bool,
+    /// Whether hints may touch no cards at all, only giving negative information.
+    pub allow_null_hints: bool,
+    /// End the game as soon as no more points can be scored, for example because all copies of a card are discarded.
+    pub end_at_max_reachable_score: bool,
+    /// Suits defined one by one. Unless empty, they replace the suits chosen by
+    /// `number_of_suits` and the `rainbow`, `dark` and `colorless` flags.
+    pub suits: Suits,
 }
 
 impl Rules {
-    pub fn allow_null_hints(&self) -> bool {
-        false
-    }
-
     pub fn get_shuffled_deck(&self, rng: &mut ChaCha20Rng) -> Deck {
         let mut deck = self.all_cards();
         deck.shuffle(rng);
@@ -392,7 +739,7 @@ impl Rules {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RuleViolation {
     InvalidCardPosition,
     InvalidReceiver,
@@ -401,75 +748,169 @@ pub enum RuleViolation {
     NoMoreHintsAvailable,
     NoMoreDiscardsAvailable,
     InvalidCardInformation,
+    /// The hinted property is not one that can be hinted with these rules.
+    InvalidProperty,
+    /// The rules do not allow hints of this kind, see [`AllowedClues`].
+    DisallowedClue,
 }
 
 impl Rules {
     pub fn used_colors(&self) -> Vec<Color> {
-        vec![
+        if !self.suits.is_empty() {
+            return self.suits.iter().map(|suit| suit.color).collect();
+        }
+
+        let mut colors = vec![
             Color::Red,
             Color::Blue,
             Color::Green,
             Color::Yellow,
             Color::White,
-        ]
+            Color::Teal,
+        ];
+        self.replace_special_suits(&mut colors);
+        colors
+    }
+
+    /// Keeps the first `number_of_suits` of `colors` and replaces the last ones by the special
+    /// suits, the dark suit last.
+    pub(crate) fn replace_special_suits(&self, colors: &mut Vec<Color>) {
+        assert!((5..=6).contains(&self.number_of_suits));
+        colors.truncate(self.number_of_suits);
+
+        let special = [
+            (self.dark, Color::Black),
+            (self.rainbow, Color::Rainbow),
+            (self.colorless, Color::Gray),
+        ];
+        for (color, (_, special)) in colors
+            .iter_mut()
+            .rev()
+            .zip(special.into_iter().filter(|&(used, _)| used))
+        {
+            *color = special;
+        }
+    }
+
+    /// Whether hints of the kind of `property` may be given.
+    pub fn allows(&self, property: Property) -> bool {
+        matches!(
+            (self.allowed_clues, property),
+            (AllowedClues::All, _)
+                | (AllowedClues::NumbersOnly, Property::Number(_))
+                | (AllowedClues::ColorsOnly, Property::Color(_))
+        )
+    }
+
+    pub fn max_clue_count(&self) -> ClueCount {
+        ClueCount::from(self.max_clues)
+    }
+
+    /// The clues given back by a discard or by completing a pile.
+    pub fn clue_recovery(&self) -> ClueCount {
+        if self.clue_starved {
+            ClueCount::HALF
+        } else {
+            ClueCount::ONE
+        }
+    }
+
+    /// The colors that can be hinted.
+    pub fn clue_colors(&self) -> Vec<Color> {
+        if !self.suits.is_empty() {
+            return self
+                .used_colors()
+                .into_iter()
+                .filter(|&color| self.suits.iter().any(|suit| suit.is_touched_by(color)))
+                .collect();
+        }
+
+        self.used_colors()
+            .into_iter()
+            .filter(|color| color.is_clue_color())
+            .collect()
+    }
+
+    /// Whether a hint of `hinted_color` touches the cards of `color`. Rainbow cards are
+    /// touched by every color, unless the suits are defined one by one.
+    pub fn touches(&self, hinted_color: Color, color: Color) -> bool {
+        if !self.suits.is_empty() {
+            return self
+                .suits
+                .get(color)
+                .is_some_and(|suit| suit.is_touched_by(hinted_color));
+        }
+
+        color == hinted_color || color == Color::Rainbow
+    }
+
+    /// How many copies of `card` are in the deck. Every card of the dark suit is unique,
+    /// and so are the ones of up-or-down suits.
+    pub fn copies(&self, card: Card) -> usize {
+        if !self.suits.is_empty() {
+            return self
+                .suits
+                .get(card.color)
+                .map_or(0, |suit| suit.copies(card.number));
+        }
+
+        match (card.color, card.number) {
+            (_, Number::Start) => usize::from(self.up_or_down),
+            (Color::Black, _) => 1,
+            (_, Number::One) if self.up_or_down => 1,
+            (_, Number::One) => 3,
+            (_, Number::Two | Number::Three | Number::Four) => 2,
+            (_, Number::Five) => 1,
+        }
     }
 
     fn all_cards(&self) -> Vec<Card> {
         let colors = self.used_colors();
 
-        let mut result = Vec::with_capacity(colors.len() * 6);
+        let mut result = Vec::with_capacity(colors.len() * 10);
 
         for color in colors {
-            result.push(Card {
-                number: Number::One,
-                color,
-            });
-            result.push(Card {
-                number: Number::One,
-                color,
-            });
-            result.push(Card {
-                number: Number::One,
-                color,
-            });
-            result.push(Card {
-                number: Number::Two,
-                color,
-            });
-            result.push(Card {
-                number: Number::Two,
-                color,
-            });
-            result.push(Card {
-                number: Number::Three,
-                color,
-            });
-            result.push(Card {
-                number: Number::Three,
-                color,
-            });
-            result.push(Card {
-                number: Number::Four,
-                color,
-            });
-            result.push(Card {
-                number: Number::Four,
-                color,
-            });
-            result.push(Card {
-                number: Number::Five,
-                color,
-            });
+            for number in Number::ALL {
+                let card = Card { number, color };
+                for _ in 0..self.copies(card) {
+                    result.push(card);
+                }
+            }
         }
 
         result
     }
 
     pub fn new() -> Rules {
+        Self::with_number_of_players(4)
+    }
+
+    pub fn with_number_of_players(number_of_players: usize) -> Rules {
+        let hand_size = match number_of_players {
+            2 | 3 => 5,
+            4 | 5 => 4,
+            6 => 3,
+            _ => panic!("Hanabi is played by 2 to 6 players, not {number_of_players}."),
+        };
+
         Rules {
-            number_of_players: 4,
-            hand_size: 4,
+            number_of_players,
+            hand_size,
             max_clues: 8,
+            max_strikes: 3,
+            strikeout_scoring: StrikeoutScoring::Zero,
+            final_round_turns_per_player: 1,
+            number_of_suits: 5,
+            rainbow: false,
+            dark: false,
+            colorless: false,
+            allowed_clues: AllowedClues::All,
+            up_or_down: false,
+            hidden_plays: false,
+            clue_starved: false,
+            allow_null_hints: false,
+            end_at_max_reachable_score: false,
+            suits: Suits::default(),
         }
     }
 }
@@ -480,16 +921,306 @@ impl Default for Rules {
     }
 }
 
-#[derive(Clone)]
+impl Rules {
+    /// Checks that a game with these rules can be set up and played.
+    pub fn validate(&self) -> Result<(), InvalidRules> {
+        if !(2..=6).contains(&self.number_of_players) {
+            Err(InvalidRules::NumberOfPlayers(self.number_of_players))?;
+        }
+
+        if !(1..=PositionSet::MAX_HAND_SIZE).contains(&self.hand_size) {
+            Err(InvalidRules::HandSize(self.hand_size))?;
+        }
+
+        let number_of_suits = if self.suits.is_empty() {
+            self.number_of_suits
+        } else {
+            self.suits.len()
+        };
+        if !(5..=6).contains(&number_of_suits) {
+            Err(InvalidRules::NumberOfSuits(number_of_suits))?;
+        }
+
+        for (index, suit) in self.suits.iter().enumerate() {
+            if self
+                .suits
+                .iter()
+                .take(index)
+                .any(|other| other.color == suit.color)
+            {
+                Err(InvalidRules::DuplicateSuit(suit.color))?;
+            }
+
+            if let Some(color) = suit
+                .touched_by()
+                .find(|&color| self.suits.get(color).is_none())
+            {
+                Err(InvalidRules::HintColor(color))?;
+            }
+
+            for number in Number::ALL {
+                let card = Card {
+                    number,
+                    color: suit.color,
+                };
+                if number == Number::Start {
+                    if suit.copies(number) > 0 && !self.up_or_down {
+                        Err(InvalidRules::UnplayableCard(card))?;
+                    }
+                } else if suit.copies(number) == 0 {
+                    Err(InvalidRules::MissingCard(card))?;
+                }
+            }
+        }
+
+        if self.max_strikes == 0 {
+            Err(InvalidRules::NoStrikes)?;
+        }
+
+        let needed = self.number_of_players * self.hand_size;
+        let available = self.all_cards().len();
+        if needed > available {
+            Err(InvalidRules::TooFewCards { needed, available })?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads rules from a TOML file with the field names of [`Rules`], like
+    ///
+    /// ```toml
+    /// number_of_players = 3
+    /// number_of_suits = 6
+    /// rainbow = true
+    /// clue_starved = true
+    /// ```
+    ///
+    /// Missing fields keep the values of [`Rules::with_number_of_players`].
+    /// Instead of `number_of_suits` and the `rainbow`, `dark` and `colorless` flags, the suits
+    /// can be defined one by one, with the copies of every number in the order of
+    /// [`Number::ALL`] and the colors whose hints touch them:
+    ///
+    /// ```toml
+    /// [[suits]]
+    /// color = "Red"
+    /// copies = [3, 2, 2, 2, 1, 0]
+    /// touched_by = ["Red"]
+    ///
+    /// [[suits]]
+    /// color = "Rainbow"
+    /// copies = [1, 1, 1, 1, 1, 0]
+    /// touched_by = ["Red", "Blue", "Green", "Yellow", "White"]
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn from_toml(toml: &str) -> Result<Rules, LoadRulesError> {
+        let table: toml::Table = toml.parse()?;
+
+        let number_of_players = match table.get("number_of_players") {
+            Some(value) => value.clone().try_into()?,
+            None => Rules::new().number_of_players,
+        };
+        if !(2..=6).contains(&number_of_players) {
+            Err(InvalidRules::NumberOfPlayers(number_of_players))?;
+        }
+
+        let mut merged = toml::Table::try_from(Rules::with_number_of_players(number_of_players))
+            .expect("rules can be written as TOML");
+        for (key, value) in table {
+            if !merged.contains_key(&key) {
+                Err(LoadRulesError::UnknownKey(key.clone()))?;
+            }
+            merged.insert(key, value);
+        }
+
+        let rules: Rules = merged.try_into()?;
+        rules.validate()?;
+        Ok(rules)
+    }
+}
+
+/// Why rules cannot be played, see [`Rules::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidRules {
+    NumberOfPlayers(usize),
+    /// Hands have between 1 and [`PositionSet::MAX_HAND_SIZE`] cards.
+    HandSize(usize),
+    NumberOfSuits(usize),
+    NoStrikes,
+    /// Two suits of [`Rules::suits`] have this color.
+    DuplicateSuit(Color),
+    /// A suit is touched by hints of this color, but there is no suit of it.
+    HintColor(Color),
+    /// A suit cannot be completed without this card.
+    MissingCard(Card),
+    /// Start cards can only be played in up-or-down games.
+    UnplayableCard(Card),
+    /// Dealing the hands needs more cards than the deck has.
+    TooFewCards {
+        needed: usize,
+        available: usize,
+    },
+}
+
+impl Display for InvalidRules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidRules::NumberOfPlayers(number) => {
+                write!(f, "Hanabi is played by 2 to 6 players, not {number}")
+            }
+            InvalidRules::HandSize(size) => write!(
+                f,
+                "hand size {size} is not between 1 and {}",
+                PositionSet::MAX_HAND_SIZE
+            ),
+            InvalidRules::NumberOfSuits(number) => {
+                write!(f, "only 5 or 6 suits are supported, not {number}")
+            }
+            InvalidRules::NoStrikes => write!(f, "the game would be lost before the first turn"),
+            InvalidRules::DuplicateSuit(color) => write!(f, "there are two suits of color {color}"),
+            InvalidRules::HintColor(color) => {
+                write!(
+                    f,
+                    "a suit is touched by {color} hints, but there is no such suit"
+                )
+            }
+            InvalidRules::MissingCard(card) => write!(f, "there is no copy of {card}"),
+            InvalidRules::UnplayableCard(card) => {
+                write!(f, "{card} can only be played with up_or_down")
+            }
+            InvalidRules::TooFewCards { needed, available } => write!(
+                f,
+                "dealing needs {needed} cards, but the deck has only {available}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidRules {}
+
+/// Error when reading rules with [`Rules::from_toml`].
+#[cfg(feature = "toml")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadRulesError {
+    Toml(toml::de::Error),
+    UnknownKey(String),
+    Invalid(InvalidRules),
+}
+
+#[cfg(feature = "toml")]
+impl Display for LoadRulesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadRulesError::Toml(error) => write!(f, "invalid TOML: {error}"),
+            LoadRulesError::UnknownKey(key) => write!(f, "unknown rule \"{key}\""),
+            LoadRulesError::Invalid(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+#[cfg(feature = "toml")]
+impl std::error::Error for LoadRulesError {}
+
+#[cfg(feature = "toml")]
+impl From<toml::de::Error> for LoadRulesError {
+    fn from(error: toml::de::Error) -> Self {
+        LoadRulesError::Toml(error)
+    }
+}
+
+#[cfg(feature = "toml")]
+impl From<InvalidRules> for LoadRulesError {
+    fn from(error: InvalidRules) -> Self {
+        LoadRulesError::Invalid(error)
+    }
+}
+
+/// The order in which a pile is built.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    /// An up-or-down pile before its direction is known.
+    Undecided,
+    Up,
+    Down,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Pile {
+    top: Option<Number>,
+    direction: Direction,
+}
+
+impl Pile {
+    /// The numbers that can be played next.
+    fn next(&self) -> Vec<Number> {
+        match (self.top, self.direction) {
+            (None, Direction::Undecided) => vec![Number::One, Number::Five, Number::Start],
+            (Some(_), Direction::Undecided) => vec![Number::Two, Number::Four],
+            (None, Direction::Up) => vec![Number::One],
+            (Some(top), Direction::Up) => top.increase().into_iter().collect(),
+            (None, Direction::Down) => vec![Number::Five],
+            (Some(top), Direction::Down) => top.decrease().into_iter().collect(),
+        }
+    }
+
+    fn with(self, number: Number) -> Self {
+        let direction = match (self.direction, number) {
+            (Direction::Undecided, Number::One | Number::Two) => Direction::Up,
+            (Direction::Undecided, Number::Four | Number::Five) => Direction::Down,
+            (direction, _) => direction,
+        };
+        Self {
+            top: Some(number),
+            direction,
+        }
+    }
+
+    fn score(&self) -> usize {
+        match (self.top, self.direction) {
+            (None, _) => 0,
+            (Some(_), Direction::Undecided) => 1,
+            (Some(top), Direction::Up) => top.score(),
+            (Some(top), Direction::Down) => 6 - top.score(),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.next().is_empty()
+    }
+
+    /// The best score if every card that is not in `discard_pile` was played.
+    fn max_reachable_score(&self, color: Color, discard_pile: &CardPile, rules: &Rules) -> usize {
+        self.next()
+            .into_iter()
+            .filter(|&number| {
+                let card = Card { number, color };
+                discard_pile.count(card) < rules.copies(card)
+            })
+            .map(|number| {
+                self.with(number)
+                    .max_reachable_score(color, discard_pile, rules)
+            })
+            .max()
+            .unwrap_or(self.score())
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Firework {
-    piles: IndexMap<Color, Option<Number>>,
+    piles: IndexMap<Color, Pile>,
 }
 
 impl Display for Firework {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (&color, &maybe_number) in &self.piles {
-            match maybe_number {
-                Some(number) => write!(f, "{} ", Card { number, color })?,
+        for (&color, pile) in &self.piles {
+            let marker = match pile.direction {
+                Direction::Down => '\u{2193}',
+                _ => ' ',
+            };
+            match pile.top {
+                Some(number) => write!(f, "{}{marker}", Card { number, color })?,
                 None => write!(f, "   ")?,
             }
         }
@@ -499,59 +1230,140 @@ impl Display for Firework {
 }
 
 impl Firework {
+    /// Piles that are built up from one.
     pub fn new(used_colors: &[Color]) -> Self {
+        Self::with_direction(used_colors, Direction::Up)
+    }
+
+    /// Piles that can be built in both directions if `rules` are up or down.
+    pub fn for_rules(rules: &Rules) -> Self {
+        let direction = if rules.up_or_down {
+            Direction::Undecided
+        } else {
+            Direction::Up
+        };
+        Self::with_direction(&rules.used_colors(), direction)
+    }
+
+    fn with_direction(used_colors: &[Color], direction: Direction) -> Self {
         let mut piles = IndexMap::with_capacity(used_colors.len());
         for &color in used_colors {
-            piles.insert(color, None);
+            piles.insert(
+                color,
+                Pile {
+                    top: None,
+                    direction,
+                },
+            );
         }
         Self { piles }
     }
 
+    /// The last played number of `color`.
+    pub fn top(&self, color: Color) -> Option<Number> {
+        self.piles[&color].top
+    }
+
+    pub fn direction(&self, color: Color) -> Direction {
+        self.piles[&color].direction
+    }
+
     pub fn is_complete(&self) -> bool {
-        self.piles.values().all(|n| n == &Some(Number::Five))
+        self.piles.values().all(Pile::is_complete)
+    }
+
+    pub fn is_pile_complete(&self, color: Color) -> bool {
+        self.piles[&color].is_complete()
     }
 
     pub fn score(&self) -> usize {
-        self.piles
-            .values()
-            .map(|mn| mn.map_or(0, |n| n.score()))
-            .sum()
+        self.piles.values().map(Pile::score).sum()
     }
 
     pub fn add(&mut self, card: Card) -> bool {
-        let current = &mut self.piles[&card.color];
+        let pile = &mut self.piles[&card.color];
 
-        if card.number.comes_after(*current) {
-            *current = Some(card.number);
+        if pile.next().contains(&card.number) {
+            *pile = pile.with(card.number);
             true
         } else {
             false
         }
     }
 
+    /// Whether playing `card` would succeed and complete its pile.
+    pub(crate) fn completes_pile(&self, card: Card) -> bool {
+        let pile = self.piles[&card.color];
+        pile.next().contains(&card.number) && pile.with(card.number).is_complete()
+    }
+
     pub(crate) fn currently_playable(&self) -> CardSet {
         let mut result = CardSet::none();
 
-        for (&color, &number) in &self.piles {
-            if let Some(card) = Card::next(color, number) {
-                result.add(card);
+        for (&color, pile) in &self.piles {
+            for number in pile.next() {
+                result.add(Card { number, color });
             }
         }
 
         result
     }
 
+    /// The score if every card that is not in `discard_pile` was played.
+    pub fn max_reachable_score(&self, discard_pile: &CardPile, rules: &Rules) -> usize {
+        self.piles
+            .iter()
+            .map(|(&color, pile)| pile.max_reachable_score(color, discard_pile, rules))
+            .sum()
+    }
+
+    /// The part of [`Firework::max_reachable_score`] on the pile of `color`, 0 for unused colors.
+    pub fn max_reachable_score_of(
+        &self,
+        color: Color,
+        discard_pile: &CardPile,
+        rules: &Rules,
+    ) -> usize {
+        self.piles.get(&color).map_or(0, |pile| {
+            pile.max_reachable_score(color, discard_pile, rules)
+        })
+    }
+
     pub fn is_playable(&self, card: Card) -> bool {
         self.currently_playable().contains(card)
     }
 
+    /// The cards that can no longer be played on their pile, because they or a card after them
+    /// were played. The start card is gone as soon as a pile is started.
+    /// A pile started with the start card can still end with the one or the five, so neither is
+    /// played until the next card decides the direction.
     pub fn already_played(&self) -> CardSet {
         let mut result = CardSet::none();
 
-        for (&color, &(mut maybe_number)) in &self.piles {
-            while let Some(number) = maybe_number {
+        for (&color, pile) in &self.piles {
+            let Some(top) = pile.top else {
+                continue;
+            };
+            let mut played = |number| {
                 result.add(Card { number, color });
-                maybe_number = number.decrease();
+            };
+            played(Number::Start);
+            match pile.direction {
+                Direction::Undecided => {}
+                Direction::Up => {
+                    let mut number = Some(top);
+                    while let Some(n) = number {
+                        played(n);
+                        number = n.decrease();
+                    }
+                }
+                Direction::Down => {
+                    let mut number = Some(top);
+                    while let Some(n) = number {
+                        played(n);
+                        number = n.increase();
+                    }
+                }
             }
         }
 
@@ -567,66 +1379,69 @@ impl Firework {
         all_surely_known_touched_cards_in_hands: &CardSet,
     ) -> CardSet {
         let mut future = self.clone();
-        for card in all_surely_known_touched_cards_in_hands.in_play_order() {
-            future.add(card);
+        // Descending piles need more than one pass.
+        let mut added = true;
+        while added {
+            added = false;
+            for card in all_surely_known_touched_cards_in_hands.in_play_order() {
+                added |= future.add(card);
+            }
         }
         future.currently_playable()
     }
 }
 
-pub struct Record {
-    rules: Rules,
-    deck: Deck,
-    actions: Vec<Action>,
-}
-
-impl Display for Record {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut state = State::new(&self.rules, self.deck.clone());
-
-        for _ in 0..self.rules.number_of_players {
-            for _ in 0..self.rules.hand_size {
-                state.draw().unwrap();
-            }
-            state.go_to_next_player();
-        }
-
-        let mut turn = 1;
+pub mod deck {
+    use std::{fmt::Display, str::FromStr};
 
-        for &action in &self.actions {
-            assert!(state.is_concluded().is_none());
+    use crate::card::{Card, ParseCardError};
 
-            writeln!(f, "\n==============\n")?;
-            writeln!(f, "{state}")?;
+    use super::Rules;
 
-            writeln!(f, "Turn {} action: {}", turn, action)?;
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Deck {
+        cards: Vec<Card>,
+    }
 
-            let (_old, _new) = state.apply_action(action, &self.rules).unwrap();
+    /// Error when parsing a deck from a line like the one written by [`Deck::to_line`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ParseDeckError {
+        InvalidCard(ParseCardError),
+        WrongCards {
+            missing: Vec<Card>,
+            superfluous: Vec<Card>,
+        },
+    }
 
-            if let Some(_old) = _old {
-                // action.add_card_information(old);
+    impl Display for ParseDeckError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                ParseDeckError::InvalidCard(error) => write!(f, "invalid card: {error}"),
+                ParseDeckError::WrongCards {
+                    missing,
+                    superfluous,
+                } => {
+                    write!(f, "deck does not match the rules, missing:")?;
+                    for card in missing {
+                        write!(f, " {}{}", card.color, card.number)?;
+                    }
+                    write!(f, ", superfluous:")?;
+                    for card in superfluous {
+                        write!(f, " {}{}", card.color, card.number)?;
+                    }
+                    Ok(())
+                }
             }
-
-            state.go_to_next_player();
-            turn += 1;
-        }
-
-        match state.is_concluded() {
-            Some(Some(score)) => writeln!(f, "Won with {score} points."),
-            Some(None) => writeln!(f, "Lost."),
-            None => panic!(),
         }
     }
-}
 
-pub mod deck {
-    use itertools::Itertools;
-
-    use crate::card::{Card, Color, Number};
+    impl std::error::Error for ParseDeckError {}
 
-    #[derive(Debug, Clone)]
-    pub struct Deck {
-        cards: Vec<Card>,
+    impl From<ParseCardError> for ParseDeckError {
+        fn from(error: ParseCardError) -> Self {
+            ParseDeckError::InvalidCard(error)
+        }
     }
 
     impl Deck {
@@ -638,6 +1453,15 @@ pub mod deck {
             self.cards.is_empty()
         }
 
+        pub fn len(&self) -> usize {
+            self.cards.len()
+        }
+
+        /// The remaining cards, with the next one to be drawn last.
+        pub fn cards(&self) -> &[Card] {
+            &self.cards
+        }
+
         pub fn new(cards: Vec<Card>) -> Self {
             Self { cards }
         }
@@ -651,103 +1475,195 @@ pub mod deck {
             result
         }
 
+        /// Panics if the line is not a valid deck. See [`Deck::parse_line`] for the fallible version.
         pub fn from_line(line: &str) -> Self {
-            let cards = line
-                .chars()
-                .tuples()
-                .map(|(c, n)| {
-                    let color = match c {
-                        'r' => Color::Red,
-                        'g' => Color::Green,
-                        'b' => Color::Blue,
-                        'y' => Color::Yellow,
-                        'w' => Color::White,
-                        _ => panic!(),
-                    };
-
-                    let number = match n {
-                        '1' => Number::One,
-                        '2' => Number::Two,
-                        '3' => Number::Three,
-                        '4' => Number::Four,
-                        '5' => Number::Five,
-                        _ => panic!(),
-                    };
-
-                    Card { number, color }
-                })
-                .collect();
-
-            Self { cards }
+            Self::parse_line(line, None).unwrap()
         }
-    }
-}
 
-pub fn record_game(
-    rules: Rules,
-    deck: Deck,
-    mut players: Vec<Box<dyn Player>>,
-) -> (Option<usize>, Record, (Duration, usize)) {
-    assert_eq!(rules.number_of_players, players.len());
+        /// Parses a line as written by [`Deck::to_line`].
+        /// If `rules` are given, also checks that the deck consists of exactly the cards used by them.
+        pub fn parse_line(line: &str, rules: Option<&Rules>) -> Result<Self, ParseDeckError> {
+            let number_of_chars = line.chars().count();
+            let mut chars = line.chars();
 
-    let mut state = State::new(&rules, deck.clone());
+            let mut cards = Vec::with_capacity(number_of_chars / 2);
+            for offset in (0..number_of_chars).step_by(2) {
+                cards.push(Card::parse_next(&mut chars, offset)?);
+            }
 
-    let mut record = Vec::new();
+            let deck = Self { cards };
 
-    for p_id in 0..players.len() {
-        for _ in 0..rules.hand_size {
-            let card = state.draw().unwrap();
-            for (pp_id, pplayer) in players.iter_mut().enumerate() {
-                pplayer.witness_draw(p_id, (p_id != pp_id).then_some(card));
+            if let Some(rules) = rules {
+                deck.check(rules)?;
             }
+
+            Ok(deck)
         }
-        state.go_to_next_player();
-    }
 
-    let mut requested_actions = 0;
-    let mut total_decision_duration = Duration::ZERO;
+        /// Checks that the deck consists of exactly the cards used by `rules`.
+        pub fn check(&self, rules: &Rules) -> Result<(), ParseDeckError> {
+            let mut superfluous = self.cards.clone();
+            let mut missing = Vec::new();
 
-    loop {
-        if let Some(score) = state.is_concluded() {
-            return (
-                score,
-                Record {
-                    rules,
-                    deck,
-                    actions: record,
-                },
-                (total_decision_duration, requested_actions),
-            );
+            for card in rules.all_cards() {
+                match superfluous.iter().position(|&c| c == card) {
+                    Some(index) => {
+                        superfluous.swap_remove(index);
+                    }
+                    None => missing.push(card),
+                }
+            }
+
+            if missing.is_empty() && superfluous.is_empty() {
+                Ok(())
+            } else {
+                Err(ParseDeckError::WrongCards {
+                    missing,
+                    superfluous,
+                })
+            }
         }
+    }
 
-        requested_actions += 1;
-        let before = Instant::now();
-        let mut action = players[state.active_player_id].request_action();
-        let after = Instant::now();
-        total_decision_duration += after - before;
+    impl FromStr for Deck {
+        type Err = ParseDeckError;
 
-        let (old, new) = state.apply_action(action, &rules).unwrap();
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Self::parse_line(s, None)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::{
+            card::{Card, Color, Number, ParseCardError},
+            state::Rules,
+        };
+
+        use super::{Deck, ParseDeckError};
+
+        #[test]
+        fn round_trip() {
+            let line = "y3y3r3b2b1w2r2w4b4y1b2r4g3w5b1y4w1w3b3y4g3w3r1r3y1g2b5b4g4w1y2g1g1g5b1g4b3r5y2w4r4g1w1r1y5r1w2y1r2g2";
+            let deck = Deck::parse_line(line, Some(&Rules::new())).unwrap();
+            assert_eq!(deck.to_line(), line);
+            assert_eq!(line.parse(), Ok(deck));
+        }
 
-        record.push(action);
+        #[test]
+        fn reports_offsets() {
+            assert_eq!(
+                Deck::parse_line("r1g2x3", None),
+                Err(ParseDeckError::InvalidCard(ParseCardError::UnknownColor {
+                    offset: 4,
+                    found: 'x'
+                }))
+            );
+            assert_eq!(
+                Deck::parse_line("r1g6", None),
+                Err(ParseDeckError::InvalidCard(ParseCardError::UnknownNumber {
+                    offset: 3,
+                    found: '6'
+                }))
+            );
+            assert_eq!(
+                Deck::parse_line("r1g", None),
+                Err(ParseDeckError::InvalidCard(ParseCardError::UnexpectedEnd {
+                    offset: 3
+                }))
+            );
+        }
 
-        if let Some(old) = old {
-            action.add_card_information(old);
+        #[test]
+        fn checks_cards() {
+            let result = Deck::parse_line("r1", Some(&Rules::new()));
+            let Err(ParseDeckError::WrongCards {
+                missing,
+                superfluous,
+            }) = result
+            else {
+                panic!()
+            };
+            assert_eq!(missing.len(), 49);
+            assert!(superfluous.is_empty());
         }
 
-        for player in &mut players {
-            player.witness_action(action, state.active_player_id);
+        #[test]
+        fn parses_single_cards() {
+            assert_eq!("w".parse(), Ok(Color::White));
+            assert_eq!("4".parse(), Ok(Number::Four));
+            assert_eq!(
+                "b5".parse(),
+                Ok(Card {
+                    number: Number::Five,
+                    color: Color::Blue
+                })
+            );
+            assert_eq!(
+                "b5 ".parse::<Card>(),
+                Err(ParseCardError::TrailingCharacter {
+                    offset: 2,
+                    found: ' '
+                })
+            );
         }
+    }
+}
 
-        if let Some(new) = new {
-            for (pp_id, pplayer) in players.iter_mut().enumerate() {
-                pplayer.witness_draw(
-                    state.active_player_id,
-                    (state.active_player_id != pp_id).then_some(new),
-                );
+#[derive(Debug)]
+pub struct GameError {
+    pub violation: RuleViolation,
+    pub seat: usize,
+    pub turn: usize,
+    pub action: Action,
+    pub record: Box<Record>,
+}
+
+impl Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Turn {}: {} attempted \"{}\", which is illegal ({:?}).",
+            self.turn,
+            player_name(self.seat),
+            self.action,
+            self.violation
+        )
+    }
+}
+
+impl std::error::Error for GameError {}
+
+#[allow(clippy::type_complexity)]
+pub fn record_game(
+    rules: Rules,
+    deck: Deck,
+    players: Vec<Box<dyn Player>>,
+) -> Result<(Option<usize>, Record, (Duration, usize)), GameError> {
+    let mut game = Game::new(rules, deck, players);
+    game.deal();
+
+    loop {
+        match game.step() {
+            TurnOutcome::Acted { .. } => {}
+            TurnOutcome::Rejected {
+                seat,
+                action,
+                violation,
+            } => {
+                return Err(GameError {
+                    violation,
+                    seat,
+                    turn: game.turn(),
+                    action,
+                    record: Box::new(game.into_record()),
+                })
+            }
+            TurnOutcome::Concluded(score) => {
+                let decision_statistics = game.decision_statistics();
+                return Ok((score, game.into_record(), decision_statistics));
             }
         }
-
-        state.go_to_next_player();
     }
 }
 
@@ -755,7 +1671,7 @@ pub fn play_game(
     rules: Rules,
     rng: &mut ChaCha20Rng,
     players: Vec<Box<dyn Player>>,
-) -> Option<usize> {
+) -> Result<Option<usize>, GameError> {
     let deck = rules.get_shuffled_deck(rng);
-    record_game(rules, deck, players).0
+    Ok(record_game(rules, deck, players)?.0)
 }
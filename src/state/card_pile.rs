@@ -1,13 +1,11 @@
 use std::fmt::Display;
 
-use crate::{
-    card::{card_set::CardSet, Card, Number},
-    player::Property,
-};
+use crate::card::{card_set::CardSet, Card, Number};
 
 use super::Rules;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CardPile {
     shifted_multiplicity_to_cards: [CardSet; 3],
 }
@@ -29,59 +27,64 @@ impl CardPile {
         self.shifted_multiplicity_to_cards[0].contains(card)
     }
 
+    /// How many copies of `card` are in the pile.
+    pub fn count(&self, card: Card) -> usize {
+        self.shifted_multiplicity_to_cards
+            .iter()
+            .filter(|cards| cards.contains(card))
+            .count()
+    }
+
     pub fn new() -> Self {
         CardPile {
             shifted_multiplicity_to_cards: [CardSet::none(); 3],
         }
     }
 
+    /// The cards that can no longer be played because all copies of them or of a card they
+    /// have to be played after are in the pile, including the cards of unused colors.
     pub fn unreachable(&self, rules: &Rules) -> CardSet {
         let mut result = CardSet::all();
         for color in rules.used_colors() {
-            let one = Card {
-                color,
-                number: Number::One,
+            let available = |number| {
+                let card = Card { color, number };
+                self.count(card) < rules.copies(card)
             };
-            if self.shifted_multiplicity_to_cards[2].contains(one) {
-                continue;
-            }
-            result.remove(one);
 
-            let two = Card {
-                color,
-                number: Number::Two,
-            };
-            if self.shifted_multiplicity_to_cards[1].contains(two) {
+            if !rules.up_or_down {
+                for number in Number::ALL {
+                    if !available(number) {
+                        break;
+                    }
+                    result.remove(Card { color, number });
+                }
                 continue;
             }
-            result.remove(two);
 
-            let three = Card {
-                color,
-                number: Number::Three,
+            let reachable = |number: Number| {
+                if number == Number::Start {
+                    return available(number);
+                }
+                let ranks = 1..=5;
+                let up = ranks
+                    .clone()
+                    .filter(|&rank| rank <= number.score())
+                    .all(|rank| {
+                        available(Number::ALL[rank - 1])
+                            || (rank == 1 && number != Number::One && available(Number::Start))
+                    });
+                let down = ranks.filter(|&rank| rank >= number.score()).all(|rank| {
+                    available(Number::ALL[rank - 1])
+                        || (rank == 5 && number != Number::Five && available(Number::Start))
+                });
+                up || down
             };
-            if self.shifted_multiplicity_to_cards[1].contains(three) {
-                continue;
-            }
-            result.remove(three);
 
-            let four = Card {
-                color,
-                number: Number::Four,
-            };
-            if self.shifted_multiplicity_to_cards[1].contains(four) {
-                continue;
+            for number in Number::ALL {
+                if reachable(number) {
+                    result.remove(Card { color, number });
+                }
             }
-            result.remove(four);
-
-            let five = Card {
-                color,
-                number: Number::Five,
-            };
-            if self.shifted_multiplicity_to_cards[0].contains(five) {
-                continue;
-            }
-            result.remove(five);
         }
 
         result
@@ -97,27 +100,14 @@ impl CardPile {
         panic!()
     }
 
-    pub(crate) fn full_sets(&self) -> CardSet {
+    /// The cards of which all copies are in the pile.
+    pub(crate) fn full_sets(&self, rules: &Rules) -> CardSet {
         let mut result = CardSet::none();
-
-        let mut ones = CardSet::with_property(Property::Number(Number::One));
-        let mut twos = CardSet::with_property(Property::Number(Number::Two));
-        let mut threes = CardSet::with_property(Property::Number(Number::Three));
-        let mut fours = CardSet::with_property(Property::Number(Number::Four));
-        let mut fives = CardSet::with_property(Property::Number(Number::Five));
-
-        ones.intersect(&self.shifted_multiplicity_to_cards[2]);
-        twos.intersect(&self.shifted_multiplicity_to_cards[1]);
-        threes.intersect(&self.shifted_multiplicity_to_cards[1]);
-        fours.intersect(&self.shifted_multiplicity_to_cards[1]);
-        fives.intersect(&self.shifted_multiplicity_to_cards[0]);
-
-        result.merge(&ones);
-        result.merge(&twos);
-        result.merge(&threes);
-        result.merge(&fours);
-        result.merge(&fives);
-
+        for card in CardSet::all_in(rules).iter() {
+            if self.count(card) >= rules.copies(card) {
+                result.add(card);
+            }
+        }
         result
     }
 }
@@ -0,0 +1,68 @@
+use std::{
+    fmt::Display,
+    ops::{Add, Sub},
+};
+
+/// A number of clues that can include half a clue, as in clue-starved games.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClueCount {
+    halves: usize,
+}
+
+impl ClueCount {
+    pub const ZERO: Self = Self { halves: 0 };
+    pub const HALF: Self = Self { halves: 1 };
+    pub const ONE: Self = Self { halves: 2 };
+
+    pub const fn from_halves(halves: usize) -> Self {
+        Self { halves }
+    }
+
+    pub const fn halves(&self) -> usize {
+        self.halves
+    }
+
+    /// The number of whole clues, rounded down.
+    pub const fn whole(&self) -> usize {
+        self.halves / 2
+    }
+
+    /// Whether at least one whole clue is left, so that a hint can be given.
+    pub const fn allows_hint(&self) -> bool {
+        self.halves >= 2
+    }
+}
+
+impl From<usize> for ClueCount {
+    fn from(clues: usize) -> Self {
+        Self { halves: 2 * clues }
+    }
+}
+
+impl Add for ClueCount {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::from_halves(self.halves + rhs.halves)
+    }
+}
+
+impl Sub for ClueCount {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_halves(self.halves - rhs.halves)
+    }
+}
+
+impl Display for ClueCount {
+    /// Whole numbers are shown without a fraction, so standard games look as before.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.halves.is_multiple_of(2) {
+            write!(f, "{}", self.whole())
+        } else {
+            write!(f, "{}.5", self.whole())
+        }
+    }
+}
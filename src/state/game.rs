@@ -0,0 +1,194 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    card::Card,
+    player::{action::Action, Player},
+};
+
+use super::{deck::Deck, record::Record, PublicState, RuleViolation, Rules, State};
+
+/// What happened during a single call of [`Game::step`].
+#[derive(Debug, Clone, Copy)]
+pub enum TurnOutcome {
+    /// The active player acted. For plays and discards, `action` contains the affected card.
+    Acted {
+        seat: usize,
+        action: Action,
+        drawn: Option<Card>,
+    },
+    /// The active player requested an action that is not allowed by the rules.
+    Rejected {
+        seat: usize,
+        action: Action,
+        violation: RuleViolation,
+    },
+    /// The game is over, either with the contained score or lost.
+    Concluded(Option<usize>),
+}
+
+/// A game that can be advanced turn by turn.
+pub struct Game {
+    rules: Rules,
+    deck: Deck,
+    state: State,
+    public_state: PublicState,
+    players: Vec<Box<dyn Player>>,
+    actions: Vec<Action>,
+    is_dealt: bool,
+    total_decision_duration: Duration,
+    requested_actions: usize,
+}
+
+impl Game {
+    pub fn new(rules: Rules, deck: Deck, players: Vec<Box<dyn Player>>) -> Self {
+        assert_eq!(rules.number_of_players, players.len());
+
+        Self {
+            rules,
+            state: State::new(&rules, deck.clone()),
+            public_state: PublicState::with_deck_size(rules, deck.len()),
+            deck,
+            players,
+            actions: Vec::new(),
+            is_dealt: false,
+            total_decision_duration: Duration::ZERO,
+            requested_actions: 0,
+        }
+    }
+
+    /// Deals the starting hands. Has to be called exactly once before the first [`Game::step`].
+    pub fn deal(&mut self) {
+        assert!(!self.is_dealt);
+
+        let deck_size = self.deck.len();
+        for player in &mut self.players {
+            player.witness_deck_size(deck_size);
+        }
+
+        for p_id in 0..self.players.len() {
+            for _ in 0..self.rules.hand_size {
+                let card = self.state.draw().unwrap();
+                self.public_state.witness_draw();
+                for (pp_id, pplayer) in self.players.iter_mut().enumerate() {
+                    pplayer.witness_draw(p_id, (p_id != pp_id).then_some(card));
+                }
+            }
+            self.state.go_to_next_player();
+        }
+
+        self.is_dealt = true;
+    }
+
+    /// Asks the active player for an action and applies it.
+    pub fn step(&mut self) -> TurnOutcome {
+        assert!(self.is_dealt);
+
+        if let Some(score) = self.state.is_concluded() {
+            return TurnOutcome::Concluded(score);
+        }
+
+        let seat = self.state.active_player_id;
+
+        self.requested_actions += 1;
+        let before = Instant::now();
+        let mut action = self.players[seat].request_action();
+        let after = Instant::now();
+        self.total_decision_duration += after - before;
+
+        let (old, new) = match self.state.apply_action(action) {
+            Ok(cards) => cards,
+            Err(violation) => {
+                return TurnOutcome::Rejected {
+                    seat,
+                    action,
+                    violation,
+                }
+            }
+        };
+
+        self.actions.push(action);
+
+        let requested_action = action;
+        if let Some(old) = old {
+            action.add_card_information(old);
+        }
+        // With hidden plays, the players only learn from which position a card was played.
+        let witnessed_action = match action {
+            Action::Play { .. } if self.rules.hidden_plays => requested_action,
+            _ => action,
+        };
+
+        self.public_state.apply_action(witnessed_action);
+
+        for player in &mut self.players {
+            player.witness_action(witnessed_action, seat);
+        }
+
+        if let Some(new) = new {
+            self.public_state.witness_draw();
+            for (pp_id, pplayer) in self.players.iter_mut().enumerate() {
+                pplayer.witness_draw(seat, (seat != pp_id).then_some(new));
+            }
+        }
+
+        self.state.go_to_next_player();
+
+        TurnOutcome::Acted {
+            seat,
+            action,
+            drawn: new,
+        }
+    }
+
+    /// `None` while the game is running, otherwise the same as [`TurnOutcome::Concluded`].
+    pub fn is_concluded(&self) -> Option<Option<usize>> {
+        self.state.is_concluded()
+    }
+
+    /// What every player knows, which does not include hidden plays.
+    pub fn public_state(&self) -> &PublicState {
+        &self.public_state
+    }
+
+    /// The engine state, including all hands.
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Every action the active player may take.
+    pub fn legal_actions(&self) -> Vec<Action> {
+        self.state.legal_actions(self.state.active_player_id)
+    }
+
+    /// Checks `action` for the active player without changing the game.
+    pub fn validate(&self, action: Action) -> Result<(), RuleViolation> {
+        self.state.validate(action)
+    }
+
+    pub fn active_seat(&self) -> usize {
+        self.state.active_player_id
+    }
+
+    /// The number of the turn that the next [`Game::step`] plays, starting at 1.
+    pub fn turn(&self) -> usize {
+        self.actions.len() + 1
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// The total time spent in [`Player::request_action`] and the number of requests.
+    pub fn decision_statistics(&self) -> (Duration, usize) {
+        (self.total_decision_duration, self.requested_actions)
+    }
+
+    /// The record of all actions so far.
+    pub fn record(&self) -> Record {
+        Record::new(self.rules, self.deck.clone(), self.actions.clone())
+    }
+
+    pub fn into_record(self) -> Record {
+        Record::new(self.rules, self.deck, self.actions)
+    }
+}
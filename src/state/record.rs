@@ -0,0 +1,331 @@
+use std::fmt::Display;
+
+use crate::{
+    card::Card,
+    player::{action::Action, player_name},
+};
+
+#[cfg(feature = "serde")]
+use super::InvalidRules;
+use super::{card_pile::CardPile, deck::Deck, RuleViolation, Rules, State};
+
+/// The complete course of a game: the rules, the deck and every action in order.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Record {
+    rules: Rules,
+    deck: Deck,
+    actions: Vec<Action>,
+}
+
+/// A single turn of a [`Record`], reconstructed by the engine.
+#[derive(Clone)]
+pub struct RecordedTurn {
+    /// Starts at 1.
+    pub turn: usize,
+    pub seat: usize,
+    /// The state in which the action was taken.
+    pub state: State,
+    pub action: Action,
+    /// The played or discarded card.
+    pub revealed: Option<Card>,
+    /// The card drawn after playing or discarding, if the deck was not empty.
+    pub drawn: Option<Card>,
+}
+
+/// Where the points missing from the maximum score went, see [`Record::lost_points`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LostPoints {
+    /// Points that became unreachable because cards were discarded.
+    pub discards: usize,
+    /// Points that became unreachable only because cards were misplayed as well.
+    pub misplays: usize,
+    /// Points that could still be reached when the game ended.
+    pub unfinished: usize,
+    /// Points of the firework that do not count because the game was lost to strikes.
+    pub strikeout: usize,
+}
+
+impl Display for LostPoints {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Lost points: {} to discards, {} to misplays, {} unfinished",
+            self.discards, self.misplays, self.unfinished
+        )?;
+        if self.strikeout > 0 {
+            write!(f, ", {} to the strikeout", self.strikeout)?;
+        }
+        write!(f, ".")
+    }
+}
+
+/// Why a [`Record`] could not be replayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The deck does not contain enough cards to deal the starting hands.
+    NotEnoughCards,
+    /// The engine rejected the recorded action.
+    Rejected {
+        turn: usize,
+        seat: usize,
+        action: Action,
+        violation: RuleViolation,
+    },
+    /// There are recorded actions after the game concluded.
+    ActionAfterConclusion { turn: usize },
+    /// The game did not conclude after the last recorded action.
+    Unfinished { turns: usize },
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::NotEnoughCards => write!(f, "Not enough cards to deal."),
+            ReplayError::Rejected {
+                turn,
+                seat,
+                action,
+                violation,
+            } => write!(
+                f,
+                "Turn {turn}: {} attempted \"{action}\", which is illegal ({violation:?}).",
+                player_name(*seat)
+            ),
+            ReplayError::ActionAfterConclusion { turn } => {
+                write!(f, "Turn {turn}: The game is already over.")
+            }
+            ReplayError::Unfinished { turns } => {
+                write!(f, "The game is not over after {turns} turns.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// Error when reading a record with [`Record::from_json`].
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum LoadRecordError {
+    Json(serde_json::Error),
+    Invalid(InvalidRules),
+}
+
+#[cfg(feature = "serde")]
+impl Display for LoadRecordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadRecordError::Json(error) => write!(f, "invalid JSON: {error}"),
+            LoadRecordError::Invalid(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for LoadRecordError {}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for LoadRecordError {
+    fn from(error: serde_json::Error) -> Self {
+        LoadRecordError::Json(error)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<InvalidRules> for LoadRecordError {
+    fn from(error: InvalidRules) -> Self {
+        LoadRecordError::Invalid(error)
+    }
+}
+
+impl Record {
+    pub fn new(rules: Rules, deck: Deck, actions: Vec<Action>) -> Self {
+        Self {
+            rules,
+            deck,
+            actions,
+        }
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// The deck before dealing.
+    pub fn deck(&self) -> &Deck {
+        &self.deck
+    }
+
+    /// The actions as requested by the players, so without card information.
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Rejects records whose rules cannot be played, see [`Rules::validate`].
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, LoadRecordError> {
+        let record: Self = serde_json::from_str(json)?;
+        record.rules.validate()?;
+        Ok(record)
+    }
+
+    fn dealt_state(&self) -> Option<State> {
+        State::dealt(&self.rules, self.deck.clone())
+    }
+
+    /// Reconstructs the game turn by turn.
+    /// Stops early if the engine rejects an action, see [`Record::replay`] to find out why.
+    pub fn turns(&self) -> impl Iterator<Item = RecordedTurn> {
+        let mut turns = Vec::with_capacity(self.actions.len());
+        self.walk(|turn| turns.push(turn));
+        turns.into_iter()
+    }
+
+    /// The state after the last action that the engine accepted, `None` if the deck is too small to deal.
+    pub fn final_state(&self) -> Option<State> {
+        self.walk(|_| {}).final_state
+    }
+
+    /// Splits the points missing from [`Rules::max_score`] by their cause, `None` if the deck is
+    /// too small to deal. Discards are blamed first, misplays only for what they made
+    /// unreachable on top.
+    pub fn lost_points(&self) -> Option<LostPoints> {
+        self.walk(|_| {}).lost_points(&self.rules)
+    }
+
+    /// Validates every recorded action against the engine and returns the outcome of the game.
+    pub fn replay(&self) -> Result<Option<usize>, ReplayError> {
+        self.walk(|_| {}).outcome
+    }
+
+    /// Replays the game once, handing every accepted turn to `visit`.
+    fn walk(&self, mut visit: impl FnMut(RecordedTurn)) -> Walk {
+        let Some(mut state) = self.dealt_state() else {
+            return Walk {
+                final_state: None,
+                discarded: CardPile::new(),
+                outcome: Err(ReplayError::NotEnoughCards),
+            };
+        };
+        let mut discarded = CardPile::new();
+
+        for (turn, &action) in (1..).zip(&self.actions) {
+            if state.is_concluded().is_some() {
+                return Walk {
+                    final_state: Some(state),
+                    discarded,
+                    outcome: Err(ReplayError::ActionAfterConclusion { turn }),
+                };
+            }
+
+            let seat = state.active_player_id;
+            let mut next = state.clone();
+            let (revealed, drawn) = match next.apply_action(action) {
+                Ok(cards) => cards,
+                Err(violation) => {
+                    return Walk {
+                        final_state: Some(state),
+                        discarded,
+                        outcome: Err(ReplayError::Rejected {
+                            turn,
+                            seat,
+                            action,
+                            violation,
+                        }),
+                    }
+                }
+            };
+            next.go_to_next_player();
+            if let (Action::Discard { .. }, Some(card)) = (action, revealed) {
+                discarded.add(&card);
+            }
+
+            visit(RecordedTurn {
+                turn,
+                seat,
+                state,
+                action,
+                revealed,
+                drawn,
+            });
+            state = next;
+        }
+
+        let outcome = state.is_concluded().ok_or(ReplayError::Unfinished {
+            turns: self.actions.len(),
+        });
+        Walk {
+            final_state: Some(state),
+            discarded,
+            outcome,
+        }
+    }
+}
+
+/// What a single pass through a [`Record`] found out.
+struct Walk {
+    final_state: Option<State>,
+    /// The cards that were discarded on purpose, without misplays.
+    discarded: CardPile,
+    outcome: Result<Option<usize>, ReplayError>,
+}
+
+impl Walk {
+    fn lost_points(&self, rules: &Rules) -> Option<LostPoints> {
+        let final_state = self.final_state.as_ref()?;
+
+        let firework = final_state.firework();
+        let reachable_without_misplays = firework.max_reachable_score(&self.discarded, rules);
+        let reachable = final_state.max_reachable_score();
+        let score = firework.score();
+
+        Some(LostPoints {
+            discards: rules.max_score() - reachable_without_misplays,
+            misplays: reachable_without_misplays - reachable,
+            unfinished: reachable - score,
+            strikeout: match final_state.is_concluded() {
+                Some(None) => score,
+                _ => 0,
+            },
+        })
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut written = Ok(());
+        let walk = self.walk(
+            |RecordedTurn {
+                 turn,
+                 state,
+                 action,
+                 ..
+             }| {
+                written = written.and_then(|()| {
+                    writeln!(f, "\n==============\n")?;
+                    writeln!(f, "{state}")?;
+
+                    writeln!(f, "Turn {} action: {}", turn, action)
+                });
+            },
+        );
+        written?;
+
+        if let Some(lost_points) = walk.lost_points(&self.rules) {
+            writeln!(f, "{lost_points}")?;
+        }
+
+        match walk.outcome {
+            Ok(Some(score)) => writeln!(f, "Won with {score} points."),
+            Ok(None) => writeln!(f, "Lost."),
+            Err(ReplayError::Unfinished { .. }) => writeln!(f, "Unfinished."),
+            Err(error) => writeln!(f, "{error}"),
+        }
+    }
+}
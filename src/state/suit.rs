@@ -0,0 +1,139 @@
+use crate::card::{Color, Number};
+
+/// A suit defined one by one in the rules, see [`Rules::suits`](super::Rules::suits).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "SuitDefinition", into = "SuitDefinition")
+)]
+pub struct Suit {
+    pub color: Color,
+    /// How many copies of every number are in the deck, in the order of [`Number::ALL`].
+    pub copies: [usize; 6],
+    /// The colors whose hints touch the cards of this suit, one bit per [`Color::index`].
+    touched_by: u16,
+}
+
+/// A [`Suit`] as written in a rules file, with `touched_by` as a list of colors.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SuitDefinition {
+    color: Color,
+    copies: [usize; 6],
+    touched_by: Vec<Color>,
+}
+
+#[cfg(feature = "serde")]
+impl From<SuitDefinition> for Suit {
+    fn from(definition: SuitDefinition) -> Self {
+        Suit::new(definition.color, definition.copies, &definition.touched_by)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Suit> for SuitDefinition {
+    fn from(suit: Suit) -> Self {
+        SuitDefinition {
+            color: suit.color,
+            copies: suit.copies,
+            touched_by: suit.touched_by().collect(),
+        }
+    }
+}
+
+impl Suit {
+    pub fn new(color: Color, copies: [usize; 6], touched_by: &[Color]) -> Self {
+        Suit {
+            color,
+            copies,
+            touched_by: touched_by
+                .iter()
+                .fold(0, |bits, color| bits | 1 << color.index()),
+        }
+    }
+
+    /// The copies of the card of this suit with `number`.
+    pub fn copies(&self, number: Number) -> usize {
+        self.copies[number.index()]
+    }
+
+    pub fn is_touched_by(&self, color: Color) -> bool {
+        self.touched_by & 1 << color.index() != 0
+    }
+
+    /// The colors whose hints touch this suit, in the order of [`Color::ALL`].
+    pub fn touched_by(&self) -> impl Iterator<Item = Color> + '_ {
+        Color::ALL
+            .into_iter()
+            .filter(|&color| self.is_touched_by(color))
+    }
+}
+
+/// The suits defined in the rules, at most as many as there are colors.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Vec<Suit>", into = "Vec<Suit>")
+)]
+pub struct Suits {
+    suits: [Option<Suit>; Color::ALL.len()],
+}
+
+impl Suits {
+    pub fn is_empty(&self) -> bool {
+        self.suits[0].is_none()
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Suit> {
+        self.suits.iter().map_while(Option::as_ref)
+    }
+
+    /// The suit of `color`, if there is one.
+    pub fn get(&self, color: Color) -> Option<&Suit> {
+        self.iter().find(|suit| suit.color == color)
+    }
+}
+
+/// Error when there are more suits than colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooManySuits(pub usize);
+
+impl std::fmt::Display for TooManySuits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} suits defined, but there are only {} colors",
+            self.0,
+            Color::ALL.len()
+        )
+    }
+}
+
+impl std::error::Error for TooManySuits {}
+
+impl TryFrom<Vec<Suit>> for Suits {
+    type Error = TooManySuits;
+
+    fn try_from(suits: Vec<Suit>) -> Result<Self, Self::Error> {
+        let mut result = Suits::default();
+        if suits.len() > result.suits.len() {
+            return Err(TooManySuits(suits.len()));
+        }
+        for (slot, suit) in result.suits.iter_mut().zip(suits) {
+            *slot = Some(suit);
+        }
+        Ok(result)
+    }
+}
+
+impl From<Suits> for Vec<Suit> {
+    fn from(suits: Suits) -> Self {
+        suits.iter().copied().collect()
+    }
+}
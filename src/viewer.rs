@@ -0,0 +1,307 @@
+//! An interactive terminal viewer for [`Record`]s.
+
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Write as _,
+    io::{self, Write},
+    rc::Rc,
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute, queue,
+    terminal::{self, ClearType},
+};
+
+use crate::{
+    card::Card,
+    player::{action::Action, basic::BasicPlayer, player_name, Player},
+    state::{
+        game::{Game, TurnOutcome},
+        record::{Record, RecordedTurn, ReplayError},
+        DisplayOptions, PublicState, State,
+    },
+};
+
+const HELP: &str = "←/→ step   Home/End first/last   <turn> Enter jump   v point of view   q quit";
+
+/// Navigation through the turns of a [`Record`], independent of the terminal.
+pub struct Viewer {
+    turns: Vec<RecordedTurn>,
+    final_state: State,
+    outcome: Result<Option<usize>, ReplayError>,
+    /// Index into `turns`, or `turns.len()` for the state after the last action.
+    position: usize,
+    point_of_view: Option<usize>,
+    /// With hidden plays, the public state every seat infers before every turn and after the
+    /// last one, see [`BasicPlayer::inferred_state`]. Empty otherwise.
+    inferred_states: Vec<Vec<PublicState>>,
+}
+
+/// A seat that plays the recorded actions, witnessed by a basic player for the point of view.
+struct Replaying {
+    next_action: Rc<Cell<Option<Action>>>,
+    witness: Option<Rc<RefCell<BasicPlayer>>>,
+}
+
+impl Player for Replaying {
+    fn witness_deck_size(&mut self, deck_size: usize) {
+        if let Some(witness) = &self.witness {
+            witness.borrow_mut().witness_deck_size(deck_size);
+        }
+    }
+
+    fn witness_action(&mut self, action: Action, player: usize) {
+        if let Some(witness) = &self.witness {
+            witness.borrow_mut().witness_action(action, player);
+        }
+    }
+
+    fn witness_draw(&mut self, player: usize, card: Option<Card>) {
+        if let Some(witness) = &self.witness {
+            witness.borrow_mut().witness_draw(player, card);
+        }
+    }
+
+    fn request_action(&self) -> Action {
+        self.next_action.take().unwrap()
+    }
+}
+
+/// The public state as `seat` infers it before every turn of `record` that the engine
+/// accepts, and after the last one.
+fn inferred_states(record: &Record, seat: usize) -> Vec<PublicState> {
+    let rules = *record.rules();
+    let next_action = Rc::new(Cell::new(None));
+    let witness = Rc::new(RefCell::new(BasicPlayer::new(rules, seat)));
+    let players = (0..rules.number_of_players)
+        .map(|id| {
+            Box::new(Replaying {
+                next_action: next_action.clone(),
+                witness: (id == seat).then(|| witness.clone()),
+            }) as Box<dyn Player>
+        })
+        .collect();
+
+    let mut game = Game::new(rules, record.deck().clone(), players);
+    game.deal();
+    let mut result = vec![witness.borrow().inferred_state().clone()];
+    for &action in record.actions() {
+        next_action.set(Some(action));
+        if !matches!(game.step(), TurnOutcome::Acted { .. }) {
+            break;
+        }
+        result.push(witness.borrow().inferred_state().clone());
+    }
+    result
+}
+
+impl Viewer {
+    /// `None` if the deck does not contain enough cards to deal.
+    pub fn new(record: &Record) -> Option<Self> {
+        let final_state = record.final_state()?;
+        let inferred_states = if record.rules().hidden_plays {
+            (0..record.rules().number_of_players)
+                .map(|seat| inferred_states(record, seat))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Some(Self {
+            turns: record.turns().collect(),
+            final_state,
+            outcome: record.replay(),
+            position: 0,
+            point_of_view: None,
+            inferred_states,
+        })
+    }
+
+    /// The turn that is about to be played in the shown state, starting at 1.
+    pub fn turn(&self) -> usize {
+        self.position + 1
+    }
+
+    /// The number of turns that can be shown, including the state after the last action.
+    pub fn number_of_turns(&self) -> usize {
+        self.turns.len() + 1
+    }
+
+    pub fn forward(&mut self) {
+        self.position = (self.position + 1).min(self.turns.len());
+    }
+
+    pub fn back(&mut self) {
+        self.position = self.position.saturating_sub(1);
+    }
+
+    /// Turns out of range are clamped to the first or last one.
+    pub fn jump_to(&mut self, turn: usize) {
+        self.position = turn.saturating_sub(1).min(self.turns.len());
+    }
+
+    /// `None` shows all cards, otherwise the cards of `seat` are hidden.
+    pub fn point_of_view(&self) -> Option<usize> {
+        self.point_of_view
+    }
+
+    pub fn set_point_of_view(&mut self, seat: Option<usize>) {
+        if let Some(seat) = seat {
+            assert!(seat < self.final_state.number_of_players());
+        }
+        self.point_of_view = seat;
+    }
+
+    /// Switches from all cards to the first seat, through every seat and back to all cards.
+    pub fn cycle_point_of_view(&mut self) {
+        self.point_of_view = match self.point_of_view {
+            None => Some(0),
+            Some(seat) if seat + 1 < self.final_state.number_of_players() => Some(seat + 1),
+            Some(_) => None,
+        };
+    }
+
+    pub fn state(&self) -> &State {
+        match self.turns.get(self.position) {
+            Some(turn) => &turn.state,
+            None => &self.final_state,
+        }
+    }
+
+    /// The shown state as text, with lines separated by `\n`.
+    pub fn render(&self) -> String {
+        let state = self.state();
+        let mut out = String::new();
+
+        let point_of_view = match self.point_of_view {
+            Some(seat) => format!("{}'s view", player_name(seat)),
+            None => "all cards".to_string(),
+        };
+        writeln!(
+            out,
+            "Turn {}/{}   {}   Deck: {}",
+            self.turn(),
+            self.number_of_turns(),
+            point_of_view,
+            state.deck().len()
+        )
+        .unwrap();
+        writeln!(out).unwrap();
+        let options = DisplayOptions {
+            hidden_seat: self.point_of_view,
+            names: true,
+            hints: true,
+        };
+        let mut display = state.display(options);
+        if let Some(inferred) = self
+            .point_of_view
+            .and_then(|seat| self.inferred_states.get(seat)?.get(self.position))
+        {
+            display = display.as_seen_in(inferred);
+        }
+        writeln!(out, "{display}").unwrap();
+
+        match self.turns.get(self.position) {
+            Some(turn) => {
+                write!(out, "{}: {}", player_name(turn.seat), turn.action).unwrap();
+                // Only plays are hidden, and only from the seat that played.
+                let is_hidden_play = !self.inferred_states.is_empty()
+                    && matches!(turn.action, Action::Play { .. })
+                    && self.point_of_view == Some(turn.seat);
+                if is_hidden_play {
+                    write!(out, " (??)").unwrap();
+                } else if let Some(card) = turn.revealed {
+                    write!(out, " ({card})").unwrap();
+                }
+                if let Some(card) = turn.drawn {
+                    if self.point_of_view == Some(turn.seat) {
+                        write!(out, ", draws a card").unwrap();
+                    } else {
+                        write!(out, ", draws {card}").unwrap();
+                    }
+                }
+                writeln!(out).unwrap();
+            }
+            None => match self.outcome {
+                Ok(Some(score)) => writeln!(out, "Won with {score} points.").unwrap(),
+                Ok(None) => writeln!(out, "Lost.").unwrap(),
+                Err(ReplayError::Unfinished { .. }) => writeln!(out, "Unfinished.").unwrap(),
+                Err(error) => writeln!(out, "{error}").unwrap(),
+            },
+        }
+
+        out
+    }
+}
+
+/// Restores the terminal even if drawing fails.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Shows `viewer` in the terminal until the user quits.
+pub fn run(mut viewer: Viewer) -> io::Result<()> {
+    let _terminal = RawTerminal::enter()?;
+    let mut stdout = io::stdout();
+    let mut input = String::new();
+
+    loop {
+        queue!(
+            stdout,
+            terminal::Clear(ClearType::All),
+            cursor::MoveTo(0, 0)
+        )?;
+        for line in viewer.render().lines() {
+            write!(stdout, "{line}\r\n")?;
+        }
+        write!(stdout, "\r\n{HELP}\r\n")?;
+        if !input.is_empty() {
+            write!(stdout, "Jump to turn: {input}")?;
+        }
+        stdout.flush()?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') => return Ok(()),
+            KeyCode::Esc if input.is_empty() => return Ok(()),
+            KeyCode::Esc => input.clear(),
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => viewer.forward(),
+            KeyCode::Left | KeyCode::Char('h') => viewer.back(),
+            KeyCode::Home => viewer.jump_to(1),
+            KeyCode::End => viewer.jump_to(viewer.number_of_turns()),
+            KeyCode::Char('v') => viewer.cycle_point_of_view(),
+            KeyCode::Char(digit) if digit.is_ascii_digit() => input.push(digit),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter => {
+                if let Ok(turn) = input.parse() {
+                    viewer.jump_to(turn);
+                }
+                input.clear();
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,123 @@
+#![cfg(feature = "cli")]
+
+use std::process::{Command, Output};
+
+fn ranabi(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ranabi"))
+        .args(args)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn generated_decks_are_valid() {
+    let output = ranabi(&["deck", "generate", "--seed", "12"]);
+    assert!(output.status.success());
+    let line = stdout(&output);
+
+    let output = ranabi(&["deck", "check", line.trim()]);
+    assert!(output.status.success());
+
+    let output = ranabi(&["--format", "json", "deck", "check", "r1r1"]);
+    assert!(!output.status.success());
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert!(json["error"].is_string());
+}
+
+#[test]
+fn saved_games_can_be_replayed() {
+    let path = std::env::temp_dir().join(format!("ranabi-cli-{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    let output = ranabi(&[
+        "--format",
+        "json",
+        "play",
+        "--players",
+        "3",
+        "--seed",
+        "5",
+        "--save",
+        path,
+    ]);
+    assert!(output.status.success());
+    let played: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+
+    let output = ranabi(&["--format", "json", "replay", path]);
+    std::fs::remove_file(path).unwrap();
+    assert!(output.status.success());
+    let replayed: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+
+    assert_eq!(played["score"], replayed["outcome"]["score"]);
+    assert_eq!(
+        played["record"]["actions"].as_array().unwrap().len(),
+        replayed["turns"].as_array().unwrap().len()
+    );
+}
+
+#[test]
+fn simulation_reports_all_games() {
+    let output = ranabi(&[
+        "--format",
+        "json",
+        "sim",
+        "--games",
+        "6",
+        "--threads",
+        "2",
+        "--players",
+        "2",
+    ]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+
+    assert_eq!(json["statistics"]["games"], 6);
+    assert!(json["mean_score"].as_f64().unwrap() <= 25.);
+}
+
+#[test]
+fn rules_are_read_from_toml_files() {
+    let output = ranabi(&[
+        "--format",
+        "json",
+        "play",
+        "--rules",
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/res/rules/clue_starved_rainbow.toml"
+        ),
+    ]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(json["max_score"], 30);
+    assert_eq!(json["record"]["rules"]["number_of_players"], 3);
+
+    let output = ranabi(&["play", "--rules", "does-not-exist.toml"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn simulation_reports_ceilings() {
+    let output = ranabi(&[
+        "--format",
+        "json",
+        "sim",
+        "--games",
+        "3",
+        "--ceilings",
+        "--solver-nodes",
+        "100",
+    ]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+
+    assert!(json["mean_ceiling"].as_f64().unwrap() >= json["mean_score"].as_f64().unwrap());
+
+    let output = ranabi(&["sim", "--games", "3"]);
+    assert!(!stdout(&output).contains("Ceiling"));
+}
@@ -0,0 +1,103 @@
+use ranabi::{
+    card::{Card, Color, Number},
+    player::{action::Action, PositionSet, Property},
+    simulate::{deck_for_game, simulate},
+    state::{game::Game, record_game, AllowedClues, RuleViolation, Rules},
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+fn dealt_game(rules: Rules) -> Game {
+    let deck = rules.get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(18));
+    let mut game = Game::new(rules, deck, rules.get_basic_player());
+    game.deal();
+    game
+}
+
+#[test]
+fn colorless_cards_are_not_touched_by_colors() {
+    let mut rules = Rules::new();
+    rules.colorless = true;
+    assert!(rules.used_colors().contains(&Color::Gray));
+    assert!(!Property::all(&rules).contains(&Property::Color(Color::Gray)));
+
+    let hand = [Card {
+        color: Color::Gray,
+        number: Number::Two,
+    }];
+    for property in Property::all(&rules) {
+        let expected = property == Property::Number(Number::Two);
+        assert_eq!(
+            expected,
+            PositionSet::matching(property, &hand, &rules).contains(1)
+        );
+    }
+
+    let game = dealt_game(rules);
+    let cards: Vec<_> = game.state().hand(1).cards().collect();
+    let hint = Action::hint(1, Property::Color(Color::Gray), &cards, &rules);
+    assert_eq!(Err(RuleViolation::InvalidProperty), game.validate(hint));
+}
+
+#[test]
+fn disallowed_clues_are_rejected() {
+    for (allowed_clues, allowed, disallowed) in [
+        (
+            AllowedClues::NumbersOnly,
+            Property::Number(Number::Three),
+            Property::Color(Color::Red),
+        ),
+        (
+            AllowedClues::ColorsOnly,
+            Property::Color(Color::Red),
+            Property::Number(Number::Three),
+        ),
+    ] {
+        let mut rules = Rules::new();
+        rules.allowed_clues = allowed_clues;
+        rules.allow_null_hints = true;
+        assert!(Property::all(&rules).contains(&allowed));
+        assert!(!Property::all(&rules).contains(&disallowed));
+
+        let game = dealt_game(rules);
+        let cards: Vec<_> = game.state().hand(1).cards().collect();
+        assert_eq!(
+            Ok(()),
+            game.validate(Action::hint(1, allowed, &cards, &rules))
+        );
+        assert_eq!(
+            Err(RuleViolation::DisallowedClue),
+            game.validate(Action::hint(1, disallowed, &cards, &rules))
+        );
+        assert!(game.legal_actions().iter().all(|action| match action {
+            Action::Hint {
+                hinted_property, ..
+            } => rules.allows(*hinted_property),
+            _ => true,
+        }));
+    }
+}
+
+#[test]
+fn basic_player_only_gives_allowed_clues() {
+    let mut colorless = Rules::with_number_of_players(3);
+    colorless.colorless = true;
+    let mut numbers_only = Rules::with_number_of_players(3);
+    numbers_only.allowed_clues = AllowedClues::NumbersOnly;
+    let mut colors_only = Rules::with_number_of_players(3);
+    colors_only.allowed_clues = AllowedClues::ColorsOnly;
+
+    for rules in [colorless, numbers_only, colors_only] {
+        let statistics = simulate(rules, |rules| rules.get_basic_player(), 18, 0..10, 2);
+        assert!(statistics.rule_violations.is_empty());
+
+        let deck = deck_for_game(&rules, 18, 0);
+        let (_, record, _) = record_game(rules, deck, rules.get_basic_player()).unwrap();
+        assert!(record.actions().iter().any(|action| match action {
+            Action::Hint {
+                hinted_property, ..
+            } => rules.allows(*hinted_property),
+            _ => false,
+        }));
+    }
+}
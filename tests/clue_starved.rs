@@ -0,0 +1,134 @@
+mod common;
+
+use common::{clue_starved_rules, scripted_game, witnessed_game};
+use ranabi::{
+    player::{action::Action, basic::BasicPlayer, Player},
+    state::{clue_count::ClueCount, game::Game, PublicState, RuleViolation, Rules},
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+#[test]
+fn clue_counts_show_half_clues() {
+    assert_eq!("0", ClueCount::ZERO.to_string());
+    assert_eq!("0.5", ClueCount::HALF.to_string());
+    assert_eq!("8", ClueCount::from(8).to_string());
+    assert_eq!("7.5", (ClueCount::from(8) - ClueCount::HALF).to_string());
+    assert!(!ClueCount::HALF.allows_hint());
+    assert!(ClueCount::ONE.allows_hint());
+}
+
+#[test]
+fn discards_give_back_half_a_clue() {
+    let rules = clue_starved_rules(4);
+    let (mut game, next_action) = scripted_game(
+        rules,
+        rules.get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(1)),
+    );
+
+    for _ in 0..rules.max_clues {
+        let hint = game
+            .legal_actions()
+            .into_iter()
+            .find(|action| matches!(action, Action::Hint { .. }))
+            .unwrap();
+        next_action.set(Some(hint));
+        game.step();
+    }
+    assert_eq!(ClueCount::ZERO, game.state().clues());
+
+    let discard = Action::Discard {
+        card: None,
+        position: 1,
+    };
+    next_action.set(Some(discard));
+    game.step();
+    assert_eq!(ClueCount::HALF, game.state().clues());
+    assert_eq!(ClueCount::HALF, game.public_state().clues);
+    assert!(game.state().to_string().contains("0.5"));
+    assert!(game
+        .legal_actions()
+        .iter()
+        .all(|action| !matches!(action, Action::Hint { .. })));
+
+    next_action.set(Some(discard));
+    game.step();
+    assert_eq!(ClueCount::ONE, game.state().clues());
+    assert_eq!(ClueCount::ONE, game.public_state().clues);
+    assert!(game
+        .legal_actions()
+        .iter()
+        .any(|action| matches!(action, Action::Hint { .. })));
+}
+
+#[test]
+fn hints_need_a_whole_clue() {
+    let mut public_state = PublicState::new(clue_starved_rules(4));
+    public_state.clues = ClueCount::HALF;
+    public_state.apply_action(Action::Discard {
+        card: None,
+        position: 1,
+    });
+    assert_eq!(ClueCount::ONE, public_state.clues);
+
+    let rules = clue_starved_rules(4);
+    let mut game = Game::new(
+        rules,
+        rules.get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(2)),
+        rules.get_basic_player(),
+    );
+    game.deal();
+    let discard = Action::Discard {
+        card: None,
+        position: 1,
+    };
+    assert_eq!(
+        Err(RuleViolation::NoMoreDiscardsAvailable),
+        game.validate(discard)
+    );
+}
+
+#[test]
+fn basic_player_does_not_hint_with_half_a_clue() {
+    let rules = clue_starved_rules(3);
+    let (mut game, next_action, players) = witnessed_game(
+        rules,
+        rules.get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(3)),
+    );
+
+    for _ in 0..rules.max_clues {
+        let hint = game
+            .legal_actions()
+            .into_iter()
+            .find(|action| matches!(action, Action::Hint { .. }))
+            .unwrap();
+        next_action.set(Some(hint));
+        game.step();
+    }
+    next_action.set(Some(Action::Discard {
+        card: None,
+        position: rules.hand_size,
+    }));
+    game.step();
+    assert_eq!(ClueCount::HALF, game.state().clues());
+
+    let seat = game.state().active_seat();
+    let action = players[seat].borrow().request_action();
+    assert!(!matches!(action, Action::Hint { .. }));
+    assert_eq!(Ok(()), game.validate(action));
+}
+
+#[test]
+fn basic_player_counts_clues_in_halves() {
+    for (rules, recovered) in [
+        (Rules::with_number_of_players(3), ClueCount::ONE),
+        (clue_starved_rules(3), ClueCount::HALF),
+    ] {
+        let player = BasicPlayer::new(rules, 0);
+        let assessment = player.assess_discard(rules.hand_size);
+        assert_eq!(
+            recovered.halves() as i8,
+            assessment.sure_influence_on_clue_count
+        );
+    }
+}
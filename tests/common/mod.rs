@@ -0,0 +1,199 @@
+//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+use std::{
+    cell::{Cell, RefCell},
+    ops::Range,
+    rc::Rc,
+};
+
+use ranabi::{
+    card::Card,
+    player::{action::Action, basic::BasicPlayer, Player},
+    simulate::deck_for_game,
+    state::{deck::Deck, game::Game, record::Record, record_game, Rules},
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+/// Plays whatever the test puts into the shared slot.
+pub struct ScriptedPlayer(pub Rc<Cell<Option<Action>>>);
+
+impl Player for ScriptedPlayer {
+    fn witness_action(&mut self, _action: Action, _player: usize) {}
+    fn witness_draw(&mut self, _player: usize, _card: Option<Card>) {}
+    fn request_action(&self) -> Action {
+        self.0.take().unwrap()
+    }
+}
+
+/// A dealt game in which every seat plays what is put into the returned slot.
+pub fn scripted_game(rules: Rules, deck: Deck) -> (Game, Rc<Cell<Option<Action>>>) {
+    let next_action = Rc::new(Cell::new(None));
+    let players = (0..rules.number_of_players)
+        .map(|_| Box::new(ScriptedPlayer(next_action.clone())) as Box<dyn Player>)
+        .collect();
+    let mut game = Game::new(rules, deck, players);
+    game.deal();
+    (game, next_action)
+}
+
+/// A seat that plays what the test puts into the shared slot while a basic player witnesses
+/// the game, so that the test can inspect what that player infers.
+pub struct WitnessedPlayer {
+    next_action: Rc<Cell<Option<Action>>>,
+    player: Rc<RefCell<BasicPlayer>>,
+}
+
+impl Player for WitnessedPlayer {
+    fn witness_deck_size(&mut self, deck_size: usize) {
+        self.player.borrow_mut().witness_deck_size(deck_size);
+    }
+    fn witness_action(&mut self, action: Action, player: usize) {
+        self.player.borrow_mut().witness_action(action, player);
+    }
+    fn witness_draw(&mut self, player: usize, card: Option<Card>) {
+        self.player.borrow_mut().witness_draw(player, card);
+    }
+    fn request_action(&self) -> Action {
+        self.next_action.take().unwrap()
+    }
+}
+
+/// Like [`scripted_game`], but every seat is also witnessed by a basic player that stays
+/// accessible through the returned handles.
+#[allow(clippy::type_complexity)]
+pub fn witnessed_game(
+    rules: Rules,
+    deck: Deck,
+) -> (
+    Game,
+    Rc<Cell<Option<Action>>>,
+    Vec<Rc<RefCell<BasicPlayer>>>,
+) {
+    let next_action = Rc::new(Cell::new(None));
+    let shared: Vec<_> = (0..rules.number_of_players)
+        .map(|seat| Rc::new(RefCell::new(BasicPlayer::new(rules, seat))))
+        .collect();
+    let players = shared
+        .iter()
+        .map(|player| {
+            Box::new(WitnessedPlayer {
+                next_action: next_action.clone(),
+                player: player.clone(),
+            }) as Box<dyn Player>
+        })
+        .collect();
+    let mut game = Game::new(rules, deck, players);
+    game.deal();
+    (game, next_action, shared)
+}
+
+/// A basic player the test can inspect while the game holds it.
+pub struct SharedPlayer(pub Rc<RefCell<BasicPlayer>>);
+
+impl Player for SharedPlayer {
+    fn witness_deck_size(&mut self, deck_size: usize) {
+        self.0.borrow_mut().witness_deck_size(deck_size);
+    }
+    fn witness_action(&mut self, action: Action, player: usize) {
+        self.0.borrow_mut().witness_action(action, player);
+    }
+    fn witness_draw(&mut self, player: usize, card: Option<Card>) {
+        self.0.borrow_mut().witness_draw(player, card);
+    }
+    fn request_action(&self) -> Action {
+        self.0.borrow().request_action()
+    }
+}
+
+/// A game of basic players that stay accessible through the returned handles.
+pub fn shared_game(rules: Rules, deck: Deck) -> (Game, Vec<Rc<RefCell<BasicPlayer>>>) {
+    let shared: Vec<_> = (0..rules.number_of_players)
+        .map(|seat| Rc::new(RefCell::new(BasicPlayer::new(rules, seat))))
+        .collect();
+    let players = shared
+        .iter()
+        .map(|player| Box::new(SharedPlayer(player.clone())) as Box<dyn Player>)
+        .collect();
+    (Game::new(rules, deck, players), shared)
+}
+
+/// A deck that deals `hands`, each written newest card first like `r1b2g3y4w5`, with the
+/// other cards of `rules` shuffled below them.
+pub fn deck_with_hands(rules: &Rules, hands: &[&str]) -> Deck {
+    let mut cards = rules
+        .get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(0))
+        .cards()
+        .to_vec();
+    let mut dealt = Vec::new();
+    for hand in hands {
+        for card in Deck::from_line(hand).cards().iter().rev() {
+            let index = cards.iter().position(|other| other == card).unwrap();
+            dealt.push(cards.remove(index));
+        }
+    }
+    cards.extend(dealt.into_iter().rev());
+    Deck::new(cards)
+}
+
+pub fn rainbow_rules(number_of_players: usize) -> Rules {
+    let mut rules = Rules::with_number_of_players(number_of_players);
+    rules.rainbow = true;
+    rules
+}
+
+pub fn dark_rules(number_of_players: usize) -> Rules {
+    let mut rules = Rules::with_number_of_players(number_of_players);
+    rules.dark = true;
+    rules
+}
+
+/// Four players with six suits, the last one rainbow if `rainbow` is set.
+pub fn six_suits(rainbow: bool) -> Rules {
+    let mut rules = Rules::new();
+    rules.number_of_suits = 6;
+    rules.rainbow = rainbow;
+    rules
+}
+
+pub fn clue_starved_rules(number_of_players: usize) -> Rules {
+    let mut rules = Rules::with_number_of_players(number_of_players);
+    rules.clue_starved = true;
+    rules
+}
+
+pub fn up_or_down_rules(number_of_players: usize) -> Rules {
+    let mut rules = Rules::with_number_of_players(number_of_players);
+    rules.up_or_down = true;
+    rules
+}
+
+/// Plays the games of [`ranabi::simulate::simulate`] with basic players and returns their
+/// records, after checking that nobody broke the rules and that the mean score is above
+/// `min_mean_score`.
+pub fn basic_games(
+    rules: Rules,
+    base_seed: u64,
+    games: Range<u64>,
+    min_mean_score: f64,
+) -> Vec<Record> {
+    let records: Vec<_> = games
+        .map(|game| {
+            let deck = deck_for_game(&rules, base_seed, game);
+            match record_game(rules, deck, rules.get_basic_player()) {
+                Ok((_, record, _)) => record,
+                Err(error) => panic!("game {game}: {error}"),
+            }
+        })
+        .collect();
+
+    let total: usize = records
+        .iter()
+        .map(|record| record.replay().unwrap().unwrap_or(0))
+        .sum();
+    let mean_score = total as f64 / records.len() as f64;
+    assert!(mean_score > min_mean_score, "mean score {mean_score}");
+
+    records
+}
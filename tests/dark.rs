@@ -0,0 +1,129 @@
+mod common;
+
+use common::{dark_rules, deck_with_hands, witnessed_game};
+use ranabi::{
+    card::{Card, Color, Number},
+    player::{action::Action, Player},
+    state::{card_pile::CardPile, Rules},
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+#[test]
+fn dark_cards_are_unique() {
+    let rules = dark_rules(4);
+    assert_eq!(
+        vec![
+            Color::Red,
+            Color::Blue,
+            Color::Green,
+            Color::Yellow,
+            Color::Black
+        ],
+        rules.used_colors()
+    );
+
+    let deck = rules.get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(17));
+    assert_eq!(45, deck.cards().len());
+    for number in [
+        Number::One,
+        Number::Two,
+        Number::Three,
+        Number::Four,
+        Number::Five,
+    ] {
+        let card = Card {
+            color: Color::Black,
+            number,
+        };
+        assert_eq!(1, rules.copies(card));
+        assert_eq!(1, deck.cards().iter().filter(|&&c| c == card).count());
+    }
+
+    let mut both = Rules::new();
+    both.number_of_suits = 6;
+    both.rainbow = true;
+    both.dark = true;
+    assert_eq!(
+        vec![
+            Color::Red,
+            Color::Blue,
+            Color::Green,
+            Color::Yellow,
+            Color::Rainbow,
+            Color::Black
+        ],
+        both.used_colors()
+    );
+}
+
+#[test]
+fn a_single_discard_makes_dark_cards_unreachable() {
+    let rules = dark_rules(4);
+    let black_three = Card {
+        color: Color::Black,
+        number: Number::Three,
+    };
+    let red_three = Card {
+        color: Color::Red,
+        number: Number::Three,
+    };
+
+    let mut pile = CardPile::new();
+    pile.add(&black_three);
+    pile.add(&red_three);
+
+    let unreachable = pile.unreachable(&rules);
+    assert!(unreachable.contains(black_three));
+    assert!(unreachable.contains(Card {
+        color: Color::Black,
+        number: Number::Five,
+    }));
+    assert!(!unreachable.contains(Card {
+        color: Color::Black,
+        number: Number::Two,
+    }));
+    assert!(!unreachable.contains(red_three));
+}
+
+#[test]
+fn basic_player_saves_dark_cards_on_chop() {
+    let rules = dark_rules(3);
+    let chop = rules.hand_size;
+    let (mut game, next_action, players) = witnessed_game(
+        rules,
+        deck_with_hands(&rules, &["b3g4y3r4g2", "r3g3y4b4k3", "b2y2r2g4b4"]),
+    );
+
+    let save = players[0].borrow().request_action();
+    assert!(matches!(
+        save,
+        Action::Hint { receiver: 1, positions, .. } if positions.contains(chop)
+    ));
+    next_action.set(Some(save));
+    game.step();
+
+    let next = players[1].borrow().request_action();
+    assert_ne!(
+        Action::Discard {
+            card: None,
+            position: chop
+        },
+        next
+    );
+}
+
+#[test]
+fn basic_player_does_not_save_a_card_with_copies_left() {
+    let rules = dark_rules(3);
+    let (_, _, players) = witnessed_game(
+        rules,
+        deck_with_hands(&rules, &["b3g4y3r4g2", "r3g3y4b4r4", "b2y2r2g4b4"]),
+    );
+
+    let action = players[0].borrow().request_action();
+    assert!(!matches!(
+        action,
+        Action::Hint { receiver: 1, positions, .. } if positions.contains(rules.hand_size)
+    ));
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use common::scripted_game;
+use ranabi::{
+    card::{Card, Color, Number},
+    player::{action::Action, Player},
+    state::{
+        card_pile::CardPile, deck::Deck, game::TurnOutcome, record_game, Firework, Rules,
+        StrikeoutScoring,
+    },
+};
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+const DECK: &str = "y3y3r3b2b1w2r2w4b4y1b2r4g3w5b1y4w1w3b3y4g3w3r1r3y1g2b5b4g4w1y2g1g1g5b1g4b3r5y2w4r4g1w1r1y5r1w2y1r2g2";
+
+/// Always plays its newest card.
+struct BlindPlayer;
+
+impl Player for BlindPlayer {
+    fn witness_action(&mut self, _action: Action, _player: usize) {}
+    fn witness_draw(&mut self, _player: usize, _card: Option<Card>) {}
+    fn request_action(&self) -> Action {
+        Action::Play {
+            card: None,
+            position: 1,
+        }
+    }
+}
+
+fn blind_game(rules: Rules) -> (Option<usize>, usize) {
+    let players = (0..rules.number_of_players)
+        .map(|_| Box::new(BlindPlayer) as Box<dyn Player>)
+        .collect();
+    let (score, record, _) = record_game(rules, Deck::from_line(DECK), players).unwrap();
+    (score, record.actions().len())
+}
+
+#[test]
+fn strikeout_scoring() {
+    let mut rules = Rules::new();
+    let (score, turns) = blind_game(rules);
+    assert_eq!(None, score);
+
+    rules.strikeout_scoring = StrikeoutScoring::KeepScore;
+    assert_eq!((Some(2), turns), blind_game(rules));
+
+    rules.max_strikes = 1;
+    let (_, fewer_turns) = blind_game(rules);
+    assert!(fewer_turns < turns);
+}
+
+#[test]
+fn final_round_length() {
+    let mut rules = Rules::new();
+    rules.max_strikes = 100;
+
+    let cards_left_after_dealing = 50 - rules.number_of_players * rules.hand_size;
+    for turns_per_player in 0..=2 {
+        rules.final_round_turns_per_player = turns_per_player;
+        let (_, turns) = blind_game(rules);
+        assert_eq!(
+            cards_left_after_dealing + rules.number_of_players * turns_per_player,
+            turns
+        );
+    }
+}
+
+#[test]
+fn max_reachable_score_ignores_played_cards() {
+    let red = |number| Card {
+        color: Color::Red,
+        number,
+    };
+
+    let mut firework = Firework::new(&Rules::new().used_colors());
+    let mut discard_pile = CardPile::new();
+    let rules = Rules::new();
+    assert_eq!(25, firework.max_reachable_score(&discard_pile, &rules));
+
+    assert!(firework.add(red(Number::One)));
+    discard_pile.add(&red(Number::One));
+    discard_pile.add(&red(Number::One));
+    assert_eq!(25, firework.max_reachable_score(&discard_pile, &rules));
+
+    discard_pile.add(&red(Number::Three));
+    assert_eq!(25, firework.max_reachable_score(&discard_pile, &rules));
+    discard_pile.add(&red(Number::Three));
+    assert_eq!(22, firework.max_reachable_score(&discard_pile, &rules));
+}
+
+#[test]
+fn games_end_once_the_max_reachable_score_is_achieved() {
+    let mut rng = ChaCha20Rng::seed_from_u64(5);
+    let mut rules = Rules::with_number_of_players(3);
+    rules.max_strikes = 100;
+    rules.end_at_max_reachable_score = true;
+
+    for _ in 0..20 {
+        let (mut game, next_action) = scripted_game(rules, rules.get_shuffled_deck(&mut rng));
+
+        loop {
+            let state = game.state();
+            let score = state.firework().score();
+            if let Some(conclusion) = game.is_concluded() {
+                assert_eq!(Some(score), conclusion);
+                assert!(score == state.max_reachable_score() || state.deck().is_empty());
+                break;
+            }
+            assert!(score < state.max_reachable_score());
+
+            next_action.set(game.legal_actions().choose(&mut rng).copied());
+            assert!(matches!(game.step(), TurnOutcome::Acted { .. }));
+        }
+    }
+}
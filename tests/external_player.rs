@@ -0,0 +1,272 @@
+use ranabi::{
+    card::{Card, Color, Number},
+    player::{action::Action, hands::VisibleHands, InvalidPosition, Player, PositionSet, Property},
+    state::{deck::Deck, record_game, PublicState, Rules},
+};
+
+/// A player that only uses the public API: it hints playable cards so that they are the focus
+/// and plays the focus of every hint it receives.
+struct FocusPlayer {
+    seat: usize,
+    public_state: PublicState,
+    hands: VisibleHands,
+    /// For each card in our hand, newest first, whether it was the focus of a hint.
+    marked: Vec<bool>,
+}
+
+impl FocusPlayer {
+    fn new(rules: Rules, seat: usize) -> Self {
+        Self {
+            seat,
+            public_state: PublicState::new(rules),
+            hands: VisibleHands::new(&rules, seat),
+            marked: Vec::new(),
+        }
+    }
+
+    fn focused_hint(&self, receiver: usize) -> Option<Action> {
+        let hand = self.hands.cards(receiver)?;
+        let (position, &card) = (1..)
+            .zip(&hand)
+            .find(|(_, &card)| self.public_state.firework.is_playable(card))?;
+
+        [Property::Number(card.number), Property::Color(card.color)]
+            .into_iter()
+            .filter_map(|property| self.hands.hint(receiver, property))
+            .find(|hint| match hint {
+                Action::Hint { positions, .. } => {
+                    positions.focus_position(PositionSet::try_new(hand.len()).unwrap())
+                        == Some(position)
+                }
+                _ => unreachable!(),
+            })
+    }
+}
+
+impl Player for FocusPlayer {
+    fn witness_action(&mut self, action: Action, player: usize) {
+        match action {
+            Action::Play { position, .. } | Action::Discard { position, .. } => {
+                if player == self.seat {
+                    self.marked.remove(position - 1);
+                }
+            }
+            Action::Hint {
+                receiver,
+                positions,
+                ..
+            } => {
+                if receiver == self.seat {
+                    if let Some(focus) = positions
+                        .focus_position(PositionSet::try_new(positions.hand_size()).unwrap())
+                    {
+                        self.marked[focus - 1] = true;
+                    }
+                }
+            }
+        }
+        self.hands.witness_action(action, player);
+        self.public_state.apply_action(action);
+    }
+
+    fn witness_deck_size(&mut self, deck_size: usize) {
+        self.public_state.deck_size = deck_size;
+    }
+
+    fn witness_draw(&mut self, player: usize, card: Option<Card>) {
+        self.public_state.witness_draw();
+        if player == self.seat {
+            self.marked.insert(0, false);
+        }
+        self.hands.witness_draw(player, card);
+    }
+
+    fn request_action(&self) -> Action {
+        if let Some(position) = (1..).zip(&self.marked).find(|(_, &m)| m).map(|(p, _)| p) {
+            return Action::Play {
+                card: None,
+                position,
+            };
+        }
+
+        let number_of_players = self.public_state.rules.number_of_players;
+        let others = (1..number_of_players).map(|offset| (self.seat + offset) % number_of_players);
+
+        if self.public_state.clues.allows_hint() {
+            if let Some(hint) = others
+                .clone()
+                .find_map(|receiver| self.focused_hint(receiver))
+            {
+                return hint;
+            }
+        }
+
+        if self.public_state.clues < self.public_state.rules.max_clue_count() {
+            return Action::Discard {
+                card: None,
+                position: self.marked.len(),
+            };
+        }
+
+        let receiver = others.into_iter().next().unwrap();
+        let newest = self.hands.hand(receiver)[0].unwrap();
+        self.hands
+            .hint(receiver, Property::Number(newest.number))
+            .unwrap()
+    }
+}
+
+#[test]
+fn external_player_completes_games() {
+    for number_of_players in 2..=5 {
+        let rules = Rules::with_number_of_players(number_of_players);
+        let deck = Deck::from_line("r4r1g1y2b4b1r3w5r1g2y2g1y3w4r3g5r1b2r5w2y1b1w3g3b5b2w3g3y1g2y5r2y1b1g4w1w1b3r4y4b3g1b4r2w4w1g4w2y3y4");
+        let players = (0..number_of_players)
+            .map(|seat| Box::new(FocusPlayer::new(rules, seat)) as Box<dyn Player>)
+            .collect();
+
+        let (_, record, _) = record_game(rules, deck, players).unwrap();
+        assert!(record
+            .actions()
+            .iter()
+            .any(|action| matches!(action, Action::Hint { .. })));
+    }
+}
+
+#[test]
+fn position_sets_fit_their_hand() {
+    assert_eq!(
+        Err(InvalidPosition::HandSize(PositionSet::MAX_HAND_SIZE + 1)),
+        PositionSet::try_new(PositionSet::MAX_HAND_SIZE + 1)
+    );
+    assert_eq!(
+        Err(InvalidPosition::Position {
+            position: 0,
+            hand_size: 4
+        }),
+        PositionSet::try_from_positions(4, [1, 0])
+    );
+
+    let mut positions = PositionSet::try_new(4).unwrap();
+    assert_eq!(
+        Err(InvalidPosition::Position {
+            position: 5,
+            hand_size: 4
+        }),
+        positions.try_insert(5)
+    );
+    assert!(positions.is_empty());
+    assert_eq!(Ok(()), positions.try_insert(4));
+    assert_eq!(Some(4), positions.smallest());
+}
+
+#[test]
+fn hints_touch_matching_cards() {
+    let rules = Rules::new();
+    let hand = [
+        Card {
+            color: Color::Red,
+            number: Number::One,
+        },
+        Card {
+            color: Color::Blue,
+            number: Number::Three,
+        },
+        Card {
+            color: Color::Red,
+            number: Number::Four,
+        },
+        Card {
+            color: Color::Green,
+            number: Number::One,
+        },
+    ];
+
+    let Action::Hint {
+        receiver,
+        positions,
+        ..
+    } = Action::hint(2, Property::Color(Color::Red), &hand, &rules)
+    else {
+        unreachable!()
+    };
+    assert_eq!(2, receiver);
+    assert_eq!(
+        PositionSet::try_from_positions(4, [1, 3]).unwrap(),
+        positions
+    );
+    assert_eq!(vec![1, 3], positions.iter().collect::<Vec<_>>());
+    assert_eq!(
+        PositionSet::try_from_positions(4, [2, 4]).unwrap(),
+        positions.inverse()
+    );
+
+    let ones = PositionSet::matching(Property::Number(Number::One), &hand, &rules);
+    assert_eq!(
+        Some(4),
+        ones.focus_position(PositionSet::try_new(4).unwrap())
+    );
+    assert_eq!(
+        Some(1),
+        ones.focus_position(PositionSet::try_from_positions(4, [4]).unwrap())
+    );
+    assert_eq!(
+        Some(1),
+        ones.focus_position(PositionSet::try_from_positions(4, [1, 4]).unwrap())
+    );
+    assert_eq!(
+        None,
+        PositionSet::try_new(4)
+            .unwrap()
+            .focus_position(PositionSet::try_from_positions(4, [2]).unwrap())
+    );
+
+    assert!(PositionSet::try_from_positions(4, [3])
+        .unwrap()
+        .is_subset_of(positions));
+    assert!(!ones.is_subset_of(positions));
+    assert!(PositionSet::try_new(4).unwrap().is_empty());
+    assert!(positions.without(positions).is_empty());
+    assert!(
+        positions
+            .inverse()
+            .without(PositionSet::try_new(4).unwrap())
+            .len()
+            == 2
+    );
+}
+
+#[test]
+fn visible_hands_hide_the_own_cards() {
+    let rules = Rules::with_number_of_players(3);
+    let mut hands = VisibleHands::new(&rules, 1);
+    let red_one = Card {
+        color: Color::Red,
+        number: Number::One,
+    };
+    let blue_two = Card {
+        color: Color::Blue,
+        number: Number::Two,
+    };
+
+    hands.witness_draw(0, Some(red_one));
+    hands.witness_draw(0, Some(blue_two));
+    hands.witness_draw(1, None);
+    assert_eq!(&[Some(blue_two), Some(red_one)], hands.hand(0));
+    assert_eq!(&[None], hands.hand(1));
+    assert_eq!(None, hands.cards(1));
+    assert_eq!(None, hands.hint(1, Property::Color(Color::Red)));
+    assert_eq!(None, hands.cards(3));
+
+    let Some(Action::Hint { positions, .. }) = hands.hint(0, Property::Color(Color::Red)) else {
+        unreachable!()
+    };
+    assert_eq!(PositionSet::try_from_positions(2, [2]).unwrap(), positions);
+
+    let play = Action::Play {
+        card: Some(red_one),
+        position: 2,
+    };
+    assert_eq!(Some(red_one), hands.witness_action(play, 0));
+    assert_eq!(&[Some(blue_two)], hands.hand(0));
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use common::shared_game;
+use ranabi::state::{
+    deck::Deck,
+    game::{Game, TurnOutcome},
+    record_game, Rules,
+};
+
+#[test]
+fn stepping_matches_record_game() {
+    let rules = Rules::new();
+    let deck = Deck::from_line("b3b3b1y5g2r4g5w2y2w3y1w2r1y4g1r2b1w1y1w5g3b2b4b1y2g2b4r1w4r3w4r2y3w3r4w1g1r5g3r3b5b2y3g1g4w1y4g4r1y1");
+
+    let (expected_score, expected_record, _) =
+        record_game(rules, deck.clone(), rules.get_basic_player()).unwrap();
+
+    let mut game = Game::new(rules, deck, rules.get_basic_player());
+    game.deal();
+
+    let mut turns = 0;
+    let score = loop {
+        assert_eq!(game.active_seat(), turns % rules.number_of_players);
+        assert_eq!(game.turn(), turns + 1);
+
+        match game.step() {
+            TurnOutcome::Acted { seat, .. } => {
+                assert_eq!(seat, turns % rules.number_of_players);
+                turns += 1;
+            }
+            TurnOutcome::Rejected { violation, .. } => panic!("{violation:?}"),
+            TurnOutcome::Concluded(score) => break score,
+        }
+    };
+
+    assert_eq!(score, expected_score);
+    assert_eq!(game.is_concluded(), Some(score));
+    assert_eq!(game.public_state().firework.score(), 20);
+    assert_eq!(game.record().to_string(), expected_record.to_string());
+}
+
+#[test]
+fn public_state_follows_deck_and_turns() {
+    let rules = Rules::new();
+    let deck = Deck::from_line("b3b3b1y5g2r4g5w2y2w3y1w2r1y4g1r2b1w1y1w5g3b2b4b1y2g2b4r1w4r3w4r2y3w3r4w1g1r5g3r3b5b2y3g1g4w1y4g4r1y1");
+
+    let mut game = Game::new(rules, deck, rules.get_basic_player());
+    assert_eq!(50, game.public_state().deck_size);
+    game.deal();
+    assert_eq!(34, game.public_state().deck_size);
+    assert_eq!(13, game.public_state().pace());
+    assert_eq!(None, game.public_state().efficiency());
+
+    while let TurnOutcome::Acted { .. } = game.step() {
+        let public_state = game.public_state();
+        assert_eq!(game.state().deck().len(), public_state.deck_size);
+        assert_eq!(game.turn(), public_state.turn);
+        assert_eq!(
+            game.state().deck().is_empty(),
+            public_state.is_final_round()
+        );
+    }
+
+    let public_state = game.public_state();
+    assert!(public_state.is_final_round());
+    assert_eq!(
+        rules.number_of_players,
+        public_state.actions_with_empty_deck
+    );
+    assert_eq!(
+        (public_state.firework.score() + 4) as isize - public_state.max_score() as isize,
+        public_state.pace()
+    );
+    assert!(public_state.efficiency().unwrap() > 0.);
+}
+
+#[test]
+fn short_decks_are_counted_by_everyone() {
+    let rules = Rules::new();
+    let deck =
+        Deck::from_line("b3b3b1y5g2r4g5w2y2w3y1w2r1y4g1r2b1w1y1w5g3b2b4b1y2g2b4r1w4r3w4r2y3w3r4w1");
+
+    let (mut game, players) = shared_game(rules, deck);
+    assert_eq!(36, game.public_state().deck_size);
+    game.deal();
+    assert_eq!(20, game.public_state().deck_size);
+
+    while let TurnOutcome::Acted { .. } = game.step() {
+        let deck_size = game.state().deck().len();
+        assert_eq!(deck_size, game.public_state().deck_size);
+        for player in &players {
+            assert_eq!(deck_size, player.borrow().public_state().deck_size);
+        }
+    }
+}
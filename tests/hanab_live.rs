@@ -0,0 +1,153 @@
+#![cfg(feature = "serde")]
+
+use std::fs;
+
+use ranabi::{
+    card::{Color, Number},
+    hanab_live::{self, HanabLiveError, HanabLiveGame},
+    player::{action::Action, Property},
+    state::{deck::Deck, record::ReplayError, record_game, AllowedClues, Rules, StrikeoutScoring},
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+fn fixture(name: &str) -> String {
+    fs::read_to_string(format!("res/hanab_live/{name}.json")).unwrap()
+}
+
+#[test]
+fn finished_game_round_trip() {
+    let json = fixture("three_players");
+
+    let record = hanab_live::from_json(&json).unwrap();
+    assert_eq!(record.rules(), &Rules::with_number_of_players(3));
+    assert_eq!(record.replay(), Ok(Some(21)));
+
+    let expected: HanabLiveGame = serde_json::from_str(&json).unwrap();
+    assert_eq!(hanab_live::export(&record).unwrap(), expected);
+}
+
+#[test]
+fn terminated_game_round_trip() {
+    let json = fixture("two_players_terminated");
+
+    let record = hanab_live::from_json(&json).unwrap();
+    assert_eq!(record.replay(), Err(ReplayError::Unfinished { turns: 4 }));
+    assert!(matches!(
+        record.actions(),
+        [
+            Action::Hint {
+                receiver: 1,
+                hinted_property: Property::Number(Number::Two),
+                ..
+            },
+            Action::Play { position: 5, .. },
+            Action::Discard { position: 5, .. },
+            Action::Hint {
+                receiver: 0,
+                hinted_property: Property::Color(Color::Red),
+                ..
+            },
+        ]
+    ));
+
+    let Action::Hint { positions, .. } = record.actions()[3] else {
+        unreachable!()
+    };
+    assert_eq!(positions.to_string(), "X---X");
+
+    let original: HanabLiveGame = serde_json::from_str(&json).unwrap();
+    let exported = hanab_live::export(&record).unwrap();
+    assert_eq!(exported.deck, original.deck);
+    assert_eq!(exported.actions, original.actions[..4]);
+}
+
+#[test]
+fn record_round_trip() {
+    let rules = Rules::new();
+    let deck = Deck::from_line("y3y3r3b2b1w2r2w4b4y1b2r4g3w5b1y4w1w3b3y4g3w3r1r3y1g2b5b4g4w1y2g1g1g5b1g4b3r5y2w4r4g1w1r1y5r1w2y1r2g2");
+    let (score, record, _) = record_game(rules, deck, rules.get_basic_player()).unwrap();
+
+    let imported = hanab_live::from_json(&hanab_live::to_json(&record).unwrap()).unwrap();
+
+    assert_eq!(imported.rules(), record.rules());
+    assert_eq!(imported.deck(), record.deck());
+    assert_eq!(imported.actions(), record.actions());
+    assert_eq!(imported.replay(), Ok(score));
+}
+
+#[test]
+fn variant_record_round_trip() {
+    for (number_of_suits, rainbow, dark, up_or_down, variant) in [
+        (5, true, false, false, "Rainbow (5 Suits)"),
+        (6, false, false, false, "6 Suits"),
+        (6, true, false, false, "Rainbow (6 Suits)"),
+        (5, false, true, false, "Black (5 Suits)"),
+        (6, false, true, false, "Black (6 Suits)"),
+        (5, false, false, true, "Up or Down (5 Suits)"),
+    ] {
+        let mut rules = Rules::with_number_of_players(3);
+        rules.number_of_suits = number_of_suits;
+        rules.rainbow = rainbow;
+        rules.dark = dark;
+        rules.up_or_down = up_or_down;
+        let deck = rules.get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(5));
+        let (score, record, _) = record_game(rules, deck, rules.get_basic_player()).unwrap();
+
+        let exported = hanab_live::export(&record).unwrap();
+        assert_eq!(
+            exported.options.as_ref().unwrap().variant.as_deref(),
+            Some(variant)
+        );
+        assert!(exported
+            .deck
+            .iter()
+            .any(|card| card.suit_index == number_of_suits - 1));
+
+        let imported = hanab_live::import(&exported).unwrap();
+        assert_eq!(imported.rules(), record.rules());
+        assert_eq!(imported.deck(), record.deck());
+        assert_eq!(imported.actions(), record.actions());
+        assert_eq!(imported.replay(), Ok(score));
+    }
+}
+
+#[test]
+fn unsupported_variant_is_rejected() {
+    let json = fixture("two_players_terminated").replace("No Variant", "Ambiguous (6 Suits)");
+    assert!(matches!(
+        hanab_live::from_json(&json),
+        Err(HanabLiveError::UnsupportedVariant(_))
+    ));
+}
+
+#[test]
+fn unsupported_rules_are_not_exported() {
+    let changes: [fn(&mut Rules); 10] = [
+        |rules| rules.max_strikes = 4,
+        |rules| rules.strikeout_scoring = StrikeoutScoring::KeepScore,
+        |rules| rules.final_round_turns_per_player = 2,
+        |rules| rules.allow_null_hints = true,
+        |rules| rules.colorless = true,
+        |rules| rules.allowed_clues = AllowedClues::NumbersOnly,
+        |rules| rules.allowed_clues = AllowedClues::ColorsOnly,
+        |rules| rules.hidden_plays = true,
+        |rules| rules.clue_starved = true,
+        |rules| {
+            rules.rainbow = true;
+            rules.dark = true;
+        },
+    ];
+    for change in changes {
+        let mut rules = Rules::with_number_of_players(3);
+        change(&mut rules);
+        let deck = rules.get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(5));
+        let (_, record, _) = record_game(rules, deck, rules.get_basic_player()).unwrap();
+
+        assert!(matches!(
+            hanab_live::export(&record),
+            Err(HanabLiveError::UnsupportedRules(_))
+        ));
+        assert!(hanab_live::to_json(&record).is_err());
+    }
+}
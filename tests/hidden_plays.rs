@@ -0,0 +1,110 @@
+use std::{cell::RefCell, rc::Rc};
+
+mod common;
+
+use common::shared_game;
+use ranabi::{
+    card::Card,
+    player::{action::Action, basic::BasicPlayer, Player},
+    simulate::deck_for_game,
+    state::{game::Game, game::TurnOutcome, Rules},
+};
+
+/// A basic player that remembers every action it witnesses.
+struct Spy {
+    player: BasicPlayer,
+    witnessed: Rc<RefCell<Vec<Action>>>,
+}
+
+impl Player for Spy {
+    fn witness_deck_size(&mut self, deck_size: usize) {
+        self.player.witness_deck_size(deck_size);
+    }
+
+    fn witness_action(&mut self, action: Action, player: usize) {
+        self.witnessed.borrow_mut().push(action);
+        self.player.witness_action(action, player);
+    }
+
+    fn witness_draw(&mut self, player: usize, card: Option<Card>) {
+        self.player.witness_draw(player, card);
+    }
+
+    fn request_action(&self) -> Action {
+        self.player.request_action()
+    }
+}
+
+fn hidden_rules() -> Rules {
+    let mut rules = Rules::with_number_of_players(3);
+    rules.hidden_plays = true;
+    rules
+}
+
+#[test]
+fn plays_are_not_revealed() {
+    let rules = hidden_rules();
+    let witnessed = Rc::new(RefCell::new(Vec::new()));
+    let mut players = rules.get_basic_player();
+    players[0] = Box::new(Spy {
+        player: BasicPlayer::new(rules, 0),
+        witnessed: witnessed.clone(),
+    });
+
+    let mut game = Game::new(rules, deck_for_game(&rules, 20, 0), players);
+    game.deal();
+
+    let mut plays = 0;
+    let score = loop {
+        match game.step() {
+            TurnOutcome::Acted { action, .. } => {
+                if let Action::Play { card, .. } = action {
+                    assert!(card.is_some());
+                    plays += 1;
+                }
+            }
+            TurnOutcome::Rejected { violation, .. } => panic!("{violation:?}"),
+            TurnOutcome::Concluded(score) => break score,
+        }
+    };
+
+    assert!(plays > 0);
+    let witnessed = witnessed.borrow();
+    assert_eq!(
+        plays,
+        witnessed
+            .iter()
+            .filter(|a| matches!(a, Action::Play { .. }))
+            .count()
+    );
+    assert!(witnessed
+        .iter()
+        .all(|action| !matches!(action, Action::Play { card: Some(_), .. })));
+
+    let public_state = game.public_state();
+    assert_eq!(0, public_state.firework.score());
+    assert_eq!(0, public_state.strikes);
+    assert_eq!(game.state().clues(), public_state.clues);
+
+    assert_eq!(Ok(score), game.record().replay());
+}
+
+#[test]
+fn basic_players_keep_the_public_state_public() {
+    let rules = hidden_rules();
+    for game_id in 0..5 {
+        let (mut game, players) = shared_game(rules, deck_for_game(&rules, 20, game_id));
+        game.deal();
+
+        loop {
+            for player in &players {
+                assert!(*player.borrow().public_state() == *game.public_state());
+            }
+            match game.step() {
+                TurnOutcome::Acted { .. } => {}
+                TurnOutcome::Rejected { violation, .. } => panic!("{violation:?}"),
+                TurnOutcome::Concluded(_) => break,
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use ranabi::{
+    player::{action::Action, PositionSet, Property},
+    state::{deck::Deck, game::Game, record_game, RuleViolation, Rules},
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+#[test]
+fn null_hints_are_only_legal_when_allowed() {
+    let deck = Deck::from_line("y3y3r3b2b1w2r2w4b4y1b2r4g3w5b1y4w1w3b3y4g3w3r1r3y1g2b5b4g4w1y2g1g1g5b1g4b3r5y2w4r4g1w1r1y5r1w2y1r2g2");
+    let mut rules = Rules::new();
+    let null_hint = |game: &Game| {
+        let cards: Vec<_> = game.state().hand(1).cards().collect();
+        Property::all(game.rules())
+            .into_iter()
+            .map(|property| Action::hint(1, property, &cards, game.rules()))
+            .find(|hint| matches!(hint, Action::Hint { positions, .. } if positions.is_empty()))
+            .unwrap()
+    };
+
+    let mut game = Game::new(rules, deck.clone(), rules.get_basic_player());
+    game.deal();
+    let hint = null_hint(&game);
+    assert_eq!(Err(RuleViolation::NullHint), game.validate(hint));
+    assert!(!game.legal_actions().contains(&hint));
+
+    rules.allow_null_hints = true;
+    let mut game = Game::new(rules, deck, rules.get_basic_player());
+    game.deal();
+    let hint = null_hint(&game);
+    assert_eq!(Ok(()), game.validate(hint));
+    assert!(game.legal_actions().contains(&hint));
+
+    let wrong_size = Action::Hint {
+        receiver: 1,
+        hinted_property: Property::Number(ranabi::card::Number::Five),
+        positions: PositionSet::try_new(3).unwrap(),
+    };
+    assert_eq!(Err(RuleViolation::IncorrectHint), game.validate(wrong_size));
+}
+
+#[test]
+fn basic_player_handles_null_hints() {
+    let mut rng = ChaCha20Rng::seed_from_u64(14);
+    let mut null_hints = 0;
+
+    for number_of_players in 2..=6 {
+        let mut rules = Rules::with_number_of_players(number_of_players);
+        rules.allow_null_hints = true;
+
+        for _ in 0..5 {
+            let deck = rules.get_shuffled_deck(&mut rng);
+            let (_, record, _) = record_game(rules, deck, rules.get_basic_player()).unwrap();
+            null_hints += record
+                .actions()
+                .iter()
+                .filter(|action| matches!(action, Action::Hint { positions, .. } if positions.is_empty()))
+                .count();
+        }
+    }
+
+    assert!(null_hints > 0);
+}
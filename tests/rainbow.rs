@@ -0,0 +1,105 @@
+mod common;
+
+use common::{rainbow_rules, witnessed_game};
+use ranabi::{
+    card::{Card, Color, Number},
+    player::{action::Action, PositionSet, Property},
+    state::{game::Game, RuleViolation, Rules},
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+#[test]
+fn rainbow_cards_are_touched_by_every_color() {
+    let rules = rainbow_rules(4);
+    assert!(rules.used_colors().contains(&Color::Rainbow));
+    assert!(!rules.used_colors().contains(&Color::White));
+    assert!(!Property::all(&rules).contains(&Property::Color(Color::Rainbow)));
+
+    let hand = [
+        Card {
+            color: Color::Rainbow,
+            number: Number::Three,
+        },
+        Card {
+            color: Color::Red,
+            number: Number::One,
+        },
+        Card {
+            color: Color::Blue,
+            number: Number::Three,
+        },
+    ];
+
+    for color in rules.clue_colors() {
+        let positions = PositionSet::matching(Property::Color(color), &hand, &rules);
+        assert!(positions.contains(1));
+    }
+    assert_eq!(
+        PositionSet::try_from_positions(3, [1, 2]).unwrap(),
+        PositionSet::matching(Property::Color(Color::Red), &hand, &rules)
+    );
+    assert_eq!(
+        PositionSet::try_from_positions(3, [1, 3]).unwrap(),
+        PositionSet::matching(Property::Number(Number::Three), &hand, &rules)
+    );
+}
+
+#[test]
+fn only_clue_colors_can_be_hinted() {
+    for (rules, forbidden) in [
+        (Rules::new(), Color::Rainbow),
+        (rainbow_rules(4), Color::Rainbow),
+        (rainbow_rules(4), Color::White),
+    ] {
+        let mut game = Game::new(
+            rules,
+            rules.get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(1)),
+            rules.get_basic_player(),
+        );
+        game.deal();
+
+        let cards: Vec<_> = game.state().hand(1).cards().collect();
+        let hint = Action::hint(1, Property::Color(forbidden), &cards, &rules);
+        assert_eq!(Err(RuleViolation::InvalidProperty), game.validate(hint));
+        assert!(!game.legal_actions().contains(&hint));
+    }
+}
+
+#[test]
+fn basic_player_infers_rainbow_from_color_hints() {
+    let rules = rainbow_rules(3);
+    let (mut game, next_action, players) = witnessed_game(
+        rules,
+        rules.get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(15)),
+    );
+
+    let cards: Vec<_> = game.state().hand(1).cards().collect();
+    let hint = Action::hint(1, Property::Color(Color::Red), &cards, &rules);
+    let Action::Hint { positions, .. } = hint else {
+        unreachable!()
+    };
+    assert!(!positions.is_empty());
+    assert!(positions.len() < cards.len());
+    next_action.set(Some(hint));
+    game.step();
+
+    let receiver = players[1].borrow();
+    let can_be = |position, color| {
+        Number::ALL.into_iter().any(|number| {
+            receiver
+                .possibilities(position)
+                .contains(Card { number, color })
+        })
+    };
+    for position in 1..=cards.len() {
+        if positions.contains(position) {
+            assert!(can_be(position, Color::Red));
+            assert!(can_be(position, Color::Rainbow));
+        } else {
+            assert!(!can_be(position, Color::Red));
+            assert!(!can_be(position, Color::Rainbow));
+            assert!(can_be(position, Color::Blue));
+        }
+    }
+}
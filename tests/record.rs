@@ -0,0 +1,149 @@
+use ranabi::{
+    card::{Card, Color, Number},
+    player::action::Action,
+    state::{
+        clue_count::ClueCount,
+        deck::Deck,
+        record::{LostPoints, Record, ReplayError},
+        record_game, PublicState, RuleViolation, Rules,
+    },
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+fn recorded_game() -> (Option<usize>, Record) {
+    let rules = Rules::new();
+    let deck = Deck::from_line("b3r2y2g5w3b5w1r4w1y5y4r4r3r1y3y1r3g3g2r1w2w4b4b2b3b2y3g4g3b1r1g4w1g1b4b1g2y4w5r5w2y1r2w4g1b1g1y2y1w3");
+    let (score, record, _) = record_game(rules, deck, rules.get_basic_player()).unwrap();
+    (score, record)
+}
+
+#[test]
+fn replay_reproduces_score() {
+    let (score, record) = recorded_game();
+    assert_eq!(record.replay(), Ok(score));
+}
+
+#[test]
+fn turns_reconstruct_the_game() {
+    let (_, record) = recorded_game();
+    let rules = record.rules();
+
+    let mut remaining_deck = record.deck().len() - rules.number_of_players * rules.hand_size;
+    let mut count = 0;
+
+    for turn in record.turns() {
+        count += 1;
+        assert_eq!(turn.turn, count);
+        assert_eq!(turn.seat, (count - 1) % rules.number_of_players);
+        assert_eq!(turn.state.active_seat(), turn.seat);
+        assert_eq!(turn.action, record.actions()[count - 1]);
+        assert_eq!(turn.state.deck().len(), remaining_deck);
+
+        match turn.action {
+            Action::Play { position, .. } | Action::Discard { position, .. } => {
+                let hand = turn.state.hand(turn.seat);
+                assert_eq!(hand.cards().nth(position - 1), turn.revealed);
+                if remaining_deck != 0 {
+                    assert_eq!(turn.drawn, turn.state.deck().cards().last().copied());
+                    remaining_deck -= 1;
+                } else {
+                    assert!(turn.drawn.is_none());
+                }
+            }
+            Action::Hint { .. } => {
+                assert!(turn.revealed.is_none());
+                assert!(turn.drawn.is_none());
+            }
+        }
+    }
+
+    assert_eq!(count, record.actions().len());
+}
+
+#[test]
+fn replay_reports_divergence() {
+    let (_, record) = recorded_game();
+
+    let mut actions = record.actions().to_vec();
+    actions[0] = Action::Discard {
+        card: None,
+        position: 1,
+    };
+    let tampered = Record::new(*record.rules(), record.deck().clone(), actions);
+    assert!(matches!(
+        tampered.replay(),
+        Err(ReplayError::Rejected {
+            turn: 1,
+            seat: 0,
+            violation: RuleViolation::NoMoreDiscardsAvailable,
+            ..
+        })
+    ));
+    assert_eq!(tampered.turns().count(), 0);
+
+    let mut actions = record.actions().to_vec();
+    actions.pop();
+    let unfinished = Record::new(*record.rules(), record.deck().clone(), actions);
+    assert_eq!(
+        unfinished.replay(),
+        Err(ReplayError::Unfinished {
+            turns: record.actions().len() - 1
+        })
+    );
+}
+
+#[test]
+fn lost_points_explain_the_score() {
+    let (_, record) = recorded_game();
+    assert_eq!(
+        Some(LostPoints {
+            discards: 0,
+            misplays: 0,
+            unfinished: 2,
+            strikeout: 0,
+        }),
+        record.lost_points()
+    );
+    assert!(record
+        .to_string()
+        .contains("Lost points: 0 to discards, 0 to misplays, 2 unfinished."));
+
+    for seed in 0..10 {
+        let rules = Rules::with_number_of_players(2);
+        let deck = rules.get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(seed));
+        let (score, record, _) = record_game(rules, deck, rules.get_basic_player()).unwrap();
+
+        let lost_points = record.lost_points().unwrap();
+        assert_eq!(
+            rules.max_score() - score.unwrap_or(0),
+            lost_points.discards
+                + lost_points.misplays
+                + lost_points.unfinished
+                + lost_points.strikeout
+        );
+    }
+}
+
+#[test]
+fn discarded_cards_lower_the_max_score() {
+    let mut public_state = PublicState::new(Rules::new());
+    assert_eq!(25, public_state.max_score());
+
+    let red_two = Card {
+        color: Color::Red,
+        number: Number::Two,
+    };
+    for _ in 0..2 {
+        public_state.clues = ClueCount::ZERO;
+        public_state.apply_action(Action::Discard {
+            card: Some(red_two),
+            position: 1,
+        });
+    }
+
+    assert_eq!(21, public_state.max_score());
+    assert_eq!(1, public_state.max_score_of(Color::Red));
+    assert_eq!(5, public_state.max_score_of(Color::Blue));
+    assert_eq!(0, public_state.max_score_of(Color::Teal));
+}
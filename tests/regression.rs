@@ -6,7 +6,7 @@ use ranabi::state::{deck::Deck, record_game, Rules};
 fn regression_test(rules: Rules, deck: Deck, expected: Option<usize>, name: &str) {
     let players = rules.get_basic_player();
 
-    let (score, record, _) = record_game(rules, deck.clone(), players);
+    let (score, record, _) = record_game(rules, deck.clone(), players).unwrap();
 
     let mut file = File::create(format!("res/regression/{name}.txt")).unwrap();
     writeln!(&mut file, "{record}").unwrap();
@@ -38,3 +38,42 @@ fn failed_2() {
     let deck = Deck::from_line("r4r1g1y2b4b1r3w5r1g2y2g1y3w4r3g5r1b2r5w2y1b1w3g3b5b2w3g3y1g2y5r2y1b1g4w1w1b3r4y4b3g1b4r2w4w1g4w2y3y4");
     regression_test(Rules::new(), deck, None, "failed_2");
 }
+
+#[test]
+fn two_players() {
+    let deck = Deck::from_line("g4w5y1b2g2r4g5w1w1r5w3b1b5y2g1r4g1b4y2r3b2y1y5y4w3w2b4b1g2g3g4r1w4g1g3y4w2r3r1r1w1b1y3y1r2y3b3w4r2b3");
+    regression_test(Rules::with_number_of_players(2), deck, None, "two_players");
+}
+
+#[test]
+fn three_players() {
+    let deck = Deck::from_line("r1b2r4w4r4y2w1b4b1g1w5g2r5r3y2g3w3w1g4y3r2w2b1g3w1g4b1b2g2w3r2b3y4b3b5g1y4g1g5w4r3y5y1y3w2r1b4r1y1y1");
+    regression_test(
+        Rules::with_number_of_players(3),
+        deck,
+        Some(21),
+        "three_players",
+    );
+}
+
+#[test]
+fn five_players() {
+    let deck = Deck::from_line("y4w4b4y1w2b3y5y2w1y3w5b4g2w4r2r1r4g3g1w3g4r3b1r1r3y3w1r1r5b2r2b1r4g4b3g1g1w1b5w2b1y1g5g3y2w3y4b2g2y1");
+    regression_test(
+        Rules::with_number_of_players(5),
+        deck,
+        Some(21),
+        "five_players",
+    );
+}
+
+#[test]
+fn six_players() {
+    let deck = Deck::from_line("r3r3r4y2g3r4b4w5y1y4w2g4g3w3g1b3b2g1w3y4r5b1y3w2g5r1b2y3y5b3r2y1y1r1w4b4b1b5w1r2w1b1g2g4w1g1y2w4g2r1");
+    regression_test(
+        Rules::with_number_of_players(6),
+        deck,
+        Some(19),
+        "six_players",
+    );
+}
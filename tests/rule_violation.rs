@@ -0,0 +1,124 @@
+mod common;
+
+use common::scripted_game;
+use ranabi::{
+    card::{Card, Number},
+    player::{action::Action, Player, PositionSet, Property},
+    state::{deck::Deck, game::TurnOutcome, record_game, RuleViolation, Rules},
+};
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+struct StubbornPlayer;
+
+impl Player for StubbornPlayer {
+    fn witness_action(&mut self, _action: Action, _player: usize) {}
+    fn witness_draw(&mut self, _player: usize, _card: Option<Card>) {}
+    fn request_action(&self) -> Action {
+        Action::Discard {
+            card: None,
+            position: 1,
+        }
+    }
+}
+
+#[test]
+fn discarding_with_all_clues_is_reported() {
+    let rules = Rules::new();
+    let deck = Deck::from_line("y3y3r3b2b1w2r2w4b4y1b2r4g3w5b1y4w1w3b3y4g3w3r1r3y1g2b5b4g4w1y2g1g1g5b1g4b3r5y2w4r4g1w1r1y5r1w2y1r2g2");
+    let players = (0..rules.number_of_players)
+        .map(|_| Box::new(StubbornPlayer) as Box<dyn Player>)
+        .collect();
+
+    let error = record_game(rules, deck, players).unwrap_err();
+
+    assert_eq!(error.violation, RuleViolation::NoMoreDiscardsAvailable);
+    assert_eq!(error.seat, 0);
+    assert_eq!(error.turn, 1);
+    assert!(matches!(error.action, Action::Discard { position: 1, .. }));
+    assert!(error.record.to_string().ends_with("Unfinished.\n"));
+}
+
+#[test]
+fn rejected_actions_leave_the_state_untouched() {
+    let rules = Rules::new();
+    let deck = Deck::from_line("y3y3r3b2b1w2r2w4b4y1b2r4g3w5b1y4w1w3b3y4g3w3r1r3y1g2b5b4g4w1y2g1g1g5b1g4b3r5y2w4r4g1w1r1y5r1w2y1r2g2");
+    let (mut game, next_action) = scripted_game(rules, deck);
+
+    let legal_actions = game.legal_actions();
+    assert!(legal_actions
+        .iter()
+        .all(|action| !matches!(action, Action::Discard { .. })));
+    assert!(legal_actions.contains(&Action::Play {
+        card: None,
+        position: 4
+    }));
+
+    let before = game.state().to_string();
+    let wrong_hint = Action::Hint {
+        receiver: 1,
+        hinted_property: Property::Number(Number::One),
+        positions: PositionSet::try_from_positions(4, [1, 2, 3, 4]).unwrap(),
+    };
+    for action in [
+        wrong_hint,
+        Action::Play {
+            card: None,
+            position: 5,
+        },
+        Action::Hint {
+            receiver: 0,
+            hinted_property: Property::Number(Number::One),
+            positions: PositionSet::try_from_positions(4, [1]).unwrap(),
+        },
+    ] {
+        let violation = game.validate(action).unwrap_err();
+        next_action.set(Some(action));
+        assert!(matches!(
+            game.step(),
+            TurnOutcome::Rejected { violation: v, .. } if v == violation
+        ));
+        assert_eq!(before, game.state().to_string());
+        assert_eq!(rules.max_clue_count(), game.state().clues());
+        assert_eq!(1, game.turn());
+    }
+}
+
+#[test]
+fn hints_to_missing_seats_are_rejected_and_shown() {
+    let rules = Rules::new();
+    let deck = Deck::from_line("y3y3r3b2b1w2r2w4b4y1b2r4g3w5b1y4w1w3b3y4g3w3r1r3y1g2b5b4g4w1y2g1g1g5b1g4b3r5y2w4r4g1w1r1y5r1w2y1r2g2");
+    let (game, _) = scripted_game(rules, deck);
+
+    let hint = Action::Hint {
+        receiver: 6,
+        hinted_property: Property::Number(Number::One),
+        positions: PositionSet::try_from_positions(4, [1]).unwrap(),
+    };
+    assert_eq!(Err(RuleViolation::InvalidReceiver), game.validate(hint));
+    assert_eq!("Hint 1 at Player 6: X---", hint.to_string());
+}
+
+#[test]
+fn random_legal_actions_are_accepted() {
+    let mut rng = ChaCha20Rng::seed_from_u64(12);
+
+    for number_of_players in 2..=6 {
+        let rules = Rules::with_number_of_players(number_of_players);
+        let (mut game, next_action) = scripted_game(rules, rules.get_shuffled_deck(&mut rng));
+
+        while game.is_concluded().is_none() {
+            let legal_actions = game.legal_actions();
+            assert!(!legal_actions.is_empty());
+            for &action in &legal_actions {
+                assert_eq!(Ok(()), game.validate(action));
+            }
+
+            next_action.set(legal_actions.choose(&mut rng).copied());
+            assert!(matches!(game.step(), TurnOutcome::Acted { .. }));
+        }
+
+        let conclusion = game.is_concluded().unwrap();
+        assert_eq!(Ok(conclusion), game.record().replay());
+    }
+}
@@ -0,0 +1,195 @@
+#![cfg(feature = "toml")]
+
+use ranabi::{
+    card::{Card, Color, Number},
+    player::{action::Action, PositionSet, Property},
+    state::{record_game, AllowedClues, InvalidRules, LoadRulesError, Rules},
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+#[test]
+fn example_rules_are_loaded() {
+    let rules = Rules::from_toml(include_str!("../res/rules/clue_starved_rainbow.toml")).unwrap();
+
+    let mut expected = Rules::with_number_of_players(3);
+    expected.number_of_suits = 6;
+    expected.rainbow = true;
+    expected.clue_starved = true;
+    assert_eq!(expected, rules);
+}
+
+#[test]
+fn missing_rules_keep_their_standard_values() {
+    assert_eq!(Rules::new(), Rules::from_toml("").unwrap());
+
+    let rules = Rules::from_toml("number_of_players = 3\nallowed_clues = \"NumbersOnly\"").unwrap();
+    assert_eq!(5, rules.hand_size);
+    assert_eq!(AllowedClues::NumbersOnly, rules.allowed_clues);
+
+    let rules = Rules::from_toml("number_of_players = 3\nhand_size = 4").unwrap();
+    assert_eq!(4, rules.hand_size);
+}
+
+#[test]
+fn impossible_rules_are_rejected() {
+    for (toml, error) in [
+        ("number_of_players = 7", InvalidRules::NumberOfPlayers(7)),
+        (
+            "hand_size = 6",
+            InvalidRules::HandSize(PositionSet::MAX_HAND_SIZE + 1),
+        ),
+        ("hand_size = 0", InvalidRules::HandSize(0)),
+        ("number_of_suits = 4", InvalidRules::NumberOfSuits(4)),
+        ("max_strikes = 0", InvalidRules::NoStrikes),
+    ] {
+        assert_eq!(Err(LoadRulesError::Invalid(error)), Rules::from_toml(toml));
+    }
+
+    assert_eq!(
+        Err(LoadRulesError::UnknownKey("rainbw".to_string())),
+        Rules::from_toml("rainbw = true")
+    );
+    assert!(matches!(
+        Rules::from_toml("max_clues = -1"),
+        Err(LoadRulesError::Toml(_))
+    ));
+}
+
+const CUSTOM_SUITS: &str = include_str!("../res/rules/custom_suits.toml");
+
+#[test]
+fn suits_can_be_defined_one_by_one() {
+    let rules = Rules::from_toml(CUSTOM_SUITS).unwrap();
+    assert_eq!(
+        vec![
+            Color::Red,
+            Color::Blue,
+            Color::Green,
+            Color::Teal,
+            Color::Black
+        ],
+        rules.used_colors()
+    );
+    assert_eq!(
+        vec![Color::Red, Color::Blue, Color::Green, Color::Black],
+        rules.clue_colors()
+    );
+
+    let card = |color, number| Card { number, color };
+    assert_eq!(3, rules.copies(card(Color::Teal, Number::One)));
+    assert_eq!(1, rules.copies(card(Color::Black, Number::Two)));
+    assert_eq!(0, rules.copies(card(Color::Yellow, Number::One)));
+    assert_eq!(0, rules.copies(card(Color::Red, Number::Start)));
+
+    let hand = [
+        card(Color::Teal, Number::One),
+        card(Color::Red, Number::Two),
+        card(Color::Black, Number::Three),
+        card(Color::Blue, Number::Four),
+    ];
+    for (hinted_color, touched) in [
+        (Color::Red, vec![1, 2]),
+        (Color::Blue, vec![1, 4]),
+        (Color::Green, vec![]),
+        (Color::Black, vec![3]),
+    ] {
+        let positions = PositionSet::matching(Property::Color(hinted_color), &hand, &rules);
+        assert_eq!(touched, positions.iter().collect::<Vec<_>>());
+    }
+
+    let deck = rules.get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(0));
+    assert_eq!(4 * 10 + 5, deck.len());
+    let (score, record, _) = record_game(rules, deck, rules.get_basic_player()).unwrap();
+    assert!(score.unwrap_or(0) <= rules.max_score());
+    assert!(record.actions().iter().all(|action| match action {
+        Action::Hint {
+            hinted_property, ..
+        } => *hinted_property != Property::Color(Color::Teal),
+        _ => true,
+    }));
+}
+
+#[test]
+fn impossible_suits_are_rejected() {
+    let black_suit = CUSTOM_SUITS.find("# Every card is unique").unwrap();
+    for (toml, error) in [
+        (
+            CUSTOM_SUITS.replace("color = \"Green\"", "color = \"Red\""),
+            InvalidRules::DuplicateSuit(Color::Red),
+        ),
+        (
+            CUSTOM_SUITS.replace("[\"Red\", \"Blue\"]", "[\"Red\", \"Yellow\"]"),
+            InvalidRules::HintColor(Color::Yellow),
+        ),
+        (
+            CUSTOM_SUITS.replace("[1, 1, 1, 1, 1, 0]", "[1, 1, 0, 1, 1, 0]"),
+            InvalidRules::MissingCard(Card {
+                number: Number::Three,
+                color: Color::Black,
+            }),
+        ),
+        (
+            CUSTOM_SUITS.replace("[1, 1, 1, 1, 1, 0]", "[1, 1, 1, 1, 1, 1]"),
+            InvalidRules::UnplayableCard(Card {
+                number: Number::Start,
+                color: Color::Black,
+            }),
+        ),
+        (
+            CUSTOM_SUITS[..black_suit].to_string(),
+            InvalidRules::NumberOfSuits(4),
+        ),
+    ] {
+        assert_eq!(Err(LoadRulesError::Invalid(error)), Rules::from_toml(&toml));
+    }
+
+    let up_or_down = CUSTOM_SUITS
+        .replace(
+            "number_of_players = 2",
+            "number_of_players = 2\nup_or_down = true",
+        )
+        .replace("[1, 1, 1, 1, 1, 0]", "[1, 1, 1, 1, 1, 1]");
+    assert!(Rules::from_toml(&up_or_down).is_ok());
+}
+
+#[test]
+fn decks_too_small_to_deal_are_rejected() {
+    let few_copies = CUSTOM_SUITS
+        .replace(
+            "number_of_players = 2",
+            "number_of_players = 6\nhand_size = 5",
+        )
+        .replace("[3, 2, 2, 2, 1, 0]", "[1, 1, 1, 1, 1, 0]");
+    assert_eq!(
+        Err(LoadRulesError::Invalid(InvalidRules::TooFewCards {
+            needed: 30,
+            available: 25
+        })),
+        Rules::from_toml(&few_copies)
+    );
+}
+
+#[test]
+fn standard_rules_are_valid() {
+    for number_of_players in 2..=6 {
+        assert_eq!(
+            Ok(()),
+            Rules::with_number_of_players(number_of_players).validate()
+        );
+    }
+}
+
+#[test]
+fn valid_rules_always_have_enough_cards_to_deal() {
+    for (dark, up_or_down) in [(false, false), (true, false), (false, true), (true, true)] {
+        let mut rules = Rules::with_number_of_players(6);
+        rules.hand_size = PositionSet::MAX_HAND_SIZE;
+        rules.dark = dark;
+        rules.up_or_down = up_or_down;
+
+        assert_eq!(Ok(()), rules.validate());
+        let deck = rules.get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(0));
+        assert!(rules.number_of_players * rules.hand_size <= deck.len());
+    }
+}
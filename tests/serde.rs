@@ -0,0 +1,76 @@
+#![cfg(feature = "serde")]
+
+use ranabi::{
+    player::PositionSet,
+    state::{
+        deck::Deck,
+        record::{LoadRecordError, Record},
+        record_game, InvalidRules, Rules,
+    },
+};
+
+#[test]
+fn record_json_round_trip() {
+    let rules = Rules::with_number_of_players(3);
+    let deck = Deck::from_line("r1b2r4w4r4y2w1b4b1g1w5g2r5r3y2g3w3w1g4y3r2w2b1g3w1g4b1b2g2w3r2b3y4b3b5g1y4g1g5w4r3y5y1y3w2r1b4r1y1y1");
+    let (score, record, _) = record_game(rules, deck, rules.get_basic_player()).unwrap();
+
+    let json = record.to_json();
+    let parsed = Record::from_json(&json).unwrap();
+
+    assert_eq!(parsed.rules(), record.rules());
+    assert_eq!(parsed.deck(), record.deck());
+    assert_eq!(parsed.actions(), record.actions());
+    assert_eq!(parsed.replay(), Ok(score));
+    assert_eq!(parsed.to_json(), json);
+}
+
+#[test]
+fn invalid_json_is_rejected() {
+    assert!(Record::from_json("{\"rules\": 4}").is_err());
+}
+
+#[test]
+fn records_need_every_rule() {
+    let rules = Rules::with_number_of_players(2);
+    let deck = Deck::from_line("r1b2r4w4r4y2w1b4b1g1w5g2r5r3y2g3w3w1g4y3r2w2b1g3w1g4b1b2g2w3r2b3y4b3b5g1y4g1g5w4r3y5y1y3w2r1b4r1y1y1");
+    let (_, record, _) = record_game(rules, deck, rules.get_basic_player()).unwrap();
+
+    let mut json: serde_json::Value = serde_json::from_str(&record.to_json()).unwrap();
+    json["rules"]
+        .as_object_mut()
+        .unwrap()
+        .remove("hand_size")
+        .unwrap();
+    assert!(Record::from_json(&json.to_string()).is_err());
+}
+
+#[test]
+fn records_with_unplayable_rules_are_rejected() {
+    let rules = Rules::with_number_of_players(2);
+    let deck = Deck::from_line("r1b2r4w4r4y2w1b4b1g1w5g2r5r3y2g3w3w1g4y3r2w2b1g3w1g4b1b2g2w3r2b3y4b3b5g1y4g1g5w4r3y5y1y3w2r1b4r1y1y1");
+    let (_, record, _) = record_game(rules, deck, rules.get_basic_player()).unwrap();
+
+    let mut json: serde_json::Value = serde_json::from_str(&record.to_json()).unwrap();
+    json["rules"]["number_of_players"] = 9.into();
+    assert!(matches!(
+        Record::from_json(&json.to_string()),
+        Err(LoadRecordError::Invalid(InvalidRules::NumberOfPlayers(9)))
+    ));
+}
+
+#[test]
+fn position_sets_must_fit_their_hand() {
+    let mut positions = PositionSet::try_new(4).unwrap();
+    positions.try_insert(2).unwrap();
+    let json = serde_json::to_string(&positions).unwrap();
+    assert_eq!(positions, serde_json::from_str(&json).unwrap());
+
+    for invalid in [
+        r#"{"positions":[true,false,true,false,false,false],"hand_size":4}"#,
+        r#"{"positions":[false,false,false,false,false,true],"hand_size":4}"#,
+        r#"{"positions":[false,false,false,false,false,false],"hand_size":6}"#,
+    ] {
+        assert!(serde_json::from_str::<PositionSet>(invalid).is_err());
+    }
+}
@@ -0,0 +1,62 @@
+use ranabi::{
+    simulate::{deck_for_game, simulate},
+    state::{record_game, Rules},
+};
+
+#[test]
+fn results_do_not_depend_on_number_of_threads() {
+    let rules = Rules::with_number_of_players(3);
+    let run = |threads| simulate(rules, |rules| rules.get_basic_player(), 7, 10..40, threads);
+
+    let serial = run(1);
+    let parallel = run(4);
+
+    assert_eq!(30, serial.games);
+    assert_eq!(serial.games, parallel.games);
+    assert_eq!(serial.score_histogram, parallel.score_histogram);
+    assert_eq!(serial.strikeouts, parallel.strikeouts);
+    assert_eq!(serial.rule_violations, parallel.rule_violations);
+    assert_eq!(serial.decisions, parallel.decisions);
+    assert_eq!(serial.mean_score(), parallel.mean_score());
+    assert_eq!(
+        30,
+        serial.score_histogram.iter().sum::<usize>()
+            + serial.strikeouts.len()
+            + serial.rule_violations.len()
+    );
+}
+
+#[test]
+fn games_can_be_reproduced() {
+    let rules = Rules::new();
+    let statistics = simulate(rules, |rules| rules.get_basic_player(), 3, 5..6, 2);
+
+    let deck = deck_for_game(&rules, 3, 5);
+    let (score, _, (_, decisions)) = record_game(rules, deck, rules.get_basic_player()).unwrap();
+
+    assert_eq!(decisions, statistics.decisions);
+    match score {
+        Some(score) => assert_eq!(1, statistics.score_histogram[score]),
+        None => assert_eq!(vec![5], statistics.strikeouts),
+    }
+}
+
+#[test]
+fn games_use_different_decks() {
+    let rules = Rules::new();
+    assert_ne!(deck_for_game(&rules, 3, 0), deck_for_game(&rules, 3, 1));
+    assert_ne!(deck_for_game(&rules, 3, 0), deck_for_game(&rules, 4, 0));
+    assert_eq!(deck_for_game(&rules, 3, 0), deck_for_game(&rules, 3, 0));
+}
+
+#[test]
+fn averages_need_games() {
+    let rules = Rules::new();
+    let statistics = simulate(rules, |rules| rules.get_basic_player(), 3, 0..0, 2);
+
+    assert_eq!(0, statistics.games);
+    assert_eq!(None, statistics.mean_score());
+    assert_eq!(None, statistics.mean_ceiling());
+    assert_eq!(None, statistics.score_relative_to_ceiling());
+    assert_eq!(None, statistics.strikeout_rate());
+}
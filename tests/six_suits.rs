@@ -0,0 +1,49 @@
+mod common;
+
+use common::{basic_games, six_suits};
+use ranabi::{
+    card::Color,
+    player::Property,
+    state::{deck::Deck, Rules},
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+#[test]
+fn teal_is_the_sixth_suit() {
+    let rules = six_suits(false);
+    assert_eq!(6, rules.used_colors().len());
+    assert!(rules.used_colors().contains(&Color::Teal));
+    assert!(Property::all(&rules).contains(&Property::Color(Color::Teal)));
+    assert_eq!(30, rules.max_score());
+
+    let deck = rules.get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(6));
+    assert_eq!(60, deck.cards().len());
+    assert_eq!(
+        Ok(deck.clone()),
+        Deck::parse_line(&deck.to_line(), Some(&rules))
+    );
+    assert!(Deck::parse_line(&deck.to_line(), Some(&Rules::new())).is_err());
+
+    let rules = six_suits(true);
+    assert!(rules.used_colors().contains(&Color::White));
+    assert!(rules.used_colors().contains(&Color::Rainbow));
+    assert!(!rules.used_colors().contains(&Color::Teal));
+}
+
+#[test]
+fn basic_player_plays_six_suits() {
+    for rainbow in [false, true] {
+        let rules = six_suits(rainbow);
+        let records = basic_games(rules, 16, 0..20, 15.);
+
+        let sixth_suit = rules.used_colors()[5];
+        assert!(records.iter().any(|record| {
+            let final_state = record.final_state().unwrap();
+            final_state.firework().top(sixth_suit).is_some()
+        }));
+        assert!(records
+            .iter()
+            .any(|record| record.replay().unwrap().is_some_and(|score| score > 25)));
+    }
+}
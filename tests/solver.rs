@@ -0,0 +1,81 @@
+use ranabi::{
+    simulate::{deck_for_game, simulate, simulate_with_ceilings},
+    solver::{solve, Budget},
+    state::{deck::Deck, record::Record, Rules},
+};
+
+fn replayed_score(rules: Rules, deck: Deck, budget: Budget) -> (usize, bool) {
+    let solution = solve(rules, deck.clone(), budget).unwrap();
+    let record = Record::new(rules, deck, solution.actions);
+    assert_eq!(Ok(Some(solution.score)), record.replay());
+    (solution.score, solution.is_optimal)
+}
+
+#[test]
+fn solutions_can_be_replayed() {
+    for number_of_players in 2..=5 {
+        let rules = Rules::with_number_of_players(number_of_players);
+        for game in 0..3 {
+            let deck = deck_for_game(&rules, 1, game);
+            assert_eq!((25, true), replayed_score(rules, deck, Budget::default()));
+        }
+    }
+}
+
+#[test]
+fn small_budgets_give_lower_bounds() {
+    let rules = Rules::with_number_of_players(2);
+    let deck = deck_for_game(&rules, 1, 8);
+
+    let (small, is_optimal) = replayed_score(rules, deck.clone(), Budget::new(100));
+    assert!(!is_optimal);
+    let (large, _) = replayed_score(rules, deck, Budget::new(2_000));
+    assert!(small <= large);
+    assert!(large < 25);
+}
+
+#[test]
+fn decks_are_too_small_to_deal() {
+    let rules = Rules::new();
+    assert_eq!(
+        None,
+        solve(rules, Deck::from_line("r1r1r1"), Budget::default())
+    );
+}
+
+#[test]
+fn statistics_compare_scores_with_ceilings() {
+    let rules = Rules::with_number_of_players(3);
+    let players = |rules: Rules| rules.get_basic_player();
+    let budget = Budget::new(1_000);
+
+    let statistics = simulate_with_ceilings(rules, players, 7, 0..10, 2, budget);
+    assert_eq!(10, statistics.ceiling_histogram.iter().sum::<usize>());
+    assert!(statistics.mean_ceiling().unwrap() >= statistics.mean_score().unwrap());
+    assert!(statistics.score_relative_to_ceiling().unwrap() <= 1.);
+    assert!(statistics.games_at_ceiling <= 10);
+
+    let serial = simulate_with_ceilings(rules, players, 7, 0..10, 1, budget);
+    assert_eq!(serial.ceiling_histogram, statistics.ceiling_histogram);
+    assert_eq!(serial.unproven_ceilings, statistics.unproven_ceilings);
+    assert_eq!(serial.games_at_ceiling, statistics.games_at_ceiling);
+
+    let without = simulate(rules, players, 7, 0..10, 2);
+    assert_eq!(without.score_histogram, statistics.score_histogram);
+    assert_eq!(0, without.ceiling_histogram.iter().sum::<usize>());
+}
+
+#[test]
+fn hopeless_positions_are_still_solved() {
+    // Without clues nobody can hint or discard, and the only card to play is a misplay that
+    // loses the game.
+    let mut rules = Rules::with_number_of_players(2);
+    rules.max_clues = 0;
+    rules.max_strikes = 1;
+    let deck = Deck::from_line("r1r1r1b1b1b1g1g1g1y1y1y1w1w1w1r2r2b2b2g2g2y2y2w2w2r3r3b3b3g3g3y3y3w3w3r4r4b4b4g4g4y4y4w4w4r5b5g5y5w5");
+
+    let solution = solve(rules, deck, Budget::default()).unwrap();
+    assert_eq!(0, solution.score);
+    assert!(solution.is_optimal);
+    assert_eq!(1, solution.actions.len());
+}
@@ -0,0 +1,162 @@
+mod common;
+
+use common::{deck_with_hands, up_or_down_rules, witnessed_game};
+use ranabi::{
+    card::{Card, Color, Number},
+    player::{action::Action, Player, Property},
+    state::{card_pile::CardPile, clue_count::ClueCount, Direction, Firework, PublicState},
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+fn red(number: Number) -> Card {
+    Card {
+        color: Color::Red,
+        number,
+    }
+}
+
+#[test]
+fn piles_can_be_built_down() {
+    let rules = up_or_down_rules(4);
+    let deck = rules.get_shuffled_deck(&mut ChaCha20Rng::seed_from_u64(19));
+    assert_eq!(45, deck.cards().len());
+
+    let mut firework = Firework::for_rules(&rules);
+    assert_eq!(Direction::Undecided, firework.direction(Color::Red));
+    assert!(firework.is_playable(red(Number::One)));
+    assert!(firework.is_playable(red(Number::Five)));
+    assert!(firework.is_playable(red(Number::Start)));
+
+    assert!(firework.add(red(Number::Five)));
+    assert_eq!(Direction::Down, firework.direction(Color::Red));
+    assert!(firework.is_playable(red(Number::Four)));
+    assert!(!firework.is_playable(red(Number::One)));
+    assert!(firework.already_played().contains(red(Number::Start)));
+
+    for number in [Number::Four, Number::Three, Number::Two] {
+        assert!(firework.add(red(number)));
+    }
+    assert!(!firework.is_pile_complete(Color::Red));
+    assert!(firework.add(red(Number::One)));
+    assert!(firework.is_pile_complete(Color::Red));
+    assert_eq!(5, firework.score());
+}
+
+#[test]
+fn start_cards_decide_with_the_next_card() {
+    let rules = up_or_down_rules(4);
+    let mut firework = Firework::for_rules(&rules);
+
+    assert!(firework.add(red(Number::Start)));
+    assert_eq!(Direction::Undecided, firework.direction(Color::Red));
+    assert_eq!(1, firework.score());
+    assert!(firework.is_playable(red(Number::Two)));
+    assert!(firework.is_playable(red(Number::Four)));
+    assert!(!firework.is_playable(red(Number::Five)));
+    // The pile can still end with either of them.
+    assert!(!firework.already_played().contains(red(Number::One)));
+    assert!(!firework.already_played().contains(red(Number::Five)));
+
+    assert!(firework.add(red(Number::Four)));
+    assert_eq!(Direction::Down, firework.direction(Color::Red));
+    assert_eq!(2, firework.score());
+    assert!(firework.already_played().contains(red(Number::Five)));
+    assert!(!firework.already_played().contains(red(Number::One)));
+
+    let blue = |number| Card {
+        color: Color::Blue,
+        number,
+    };
+    assert!(firework.add(blue(Number::Start)));
+    assert!(firework.add(blue(Number::Two)));
+    assert_eq!(Direction::Up, firework.direction(Color::Blue));
+    assert!(firework.already_played().contains(blue(Number::One)));
+    assert!(!firework.already_played().contains(blue(Number::Five)));
+}
+
+#[test]
+fn discards_are_judged_in_both_directions() {
+    let rules = up_or_down_rules(4);
+    let firework = Firework::for_rules(&rules);
+
+    let mut discard_pile = CardPile::new();
+    discard_pile.add(&red(Number::One));
+    let unreachable = discard_pile.unreachable(&rules);
+    assert!(unreachable.contains(red(Number::One)));
+    assert!(!unreachable.contains(red(Number::Two)));
+    assert_eq!(25, firework.max_reachable_score(&discard_pile, &rules));
+
+    discard_pile.add(&red(Number::Five));
+    let unreachable = discard_pile.unreachable(&rules);
+    assert!(!unreachable.contains(red(Number::Three)));
+    assert_eq!(24, firework.max_reachable_score(&discard_pile, &rules));
+
+    discard_pile.add(&red(Number::Start));
+    let unreachable = discard_pile.unreachable(&rules);
+    assert!(unreachable.contains(red(Number::Two)));
+    assert!(unreachable.contains(red(Number::Four)));
+    assert_eq!(20, firework.max_reachable_score(&discard_pile, &rules));
+}
+
+#[test]
+fn completing_a_descending_pile_gives_a_clue() {
+    let rules = up_or_down_rules(4);
+    let mut public_state = PublicState::new(rules);
+    public_state.clues = ClueCount::from(5);
+
+    for number in [
+        Number::Five,
+        Number::Four,
+        Number::Three,
+        Number::Two,
+        Number::One,
+    ] {
+        public_state.apply_action(Action::Play {
+            card: Some(red(number)),
+            position: 1,
+        });
+    }
+    assert_eq!(ClueCount::from(6), public_state.clues);
+    assert_eq!(0, public_state.strikes);
+}
+
+#[test]
+fn basic_player_plays_start_cards_and_fives_on_undecided_piles() {
+    let rules = up_or_down_rules(3);
+    for card in [red(Number::Start), red(Number::Five)] {
+        let (mut game, next_action, players) = witnessed_game(
+            rules,
+            deck_with_hands(
+                &rules,
+                &[
+                    "b2g3y3w4g2",
+                    &format!("r{}b3g4y4w3", card.number),
+                    "b4y2w2g4b3",
+                ],
+            ),
+        );
+        assert_eq!(
+            Direction::Undecided,
+            game.state().firework().direction(Color::Red)
+        );
+
+        let cards: Vec<_> = game.state().hand(1).cards().collect();
+        next_action.set(Some(Action::hint(
+            1,
+            Property::Color(Color::Red),
+            &cards,
+            &rules,
+        )));
+        game.step();
+
+        let play = Action::Play {
+            card: None,
+            position: 1,
+        };
+        assert_eq!(play, players[1].borrow().request_action());
+        next_action.set(Some(play));
+        game.step();
+        assert_eq!(Some(card.number), game.state().firework().top(Color::Red));
+    }
+}
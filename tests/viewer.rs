@@ -0,0 +1,122 @@
+#![cfg(feature = "tui")]
+
+use ranabi::{
+    player::action::Action,
+    simulate::deck_for_game,
+    state::{deck::Deck, record_game, Rules},
+    viewer::Viewer,
+};
+
+fn failed_2() -> Viewer {
+    let rules = Rules::new();
+    let deck = Deck::from_line("r4r1g1y2b4b1r3w5r1g2y2g1y3w4r3g5r1b2r5w2y1b1w3g3b5b2w3g3y1g2y5r2y1b1g4w1w1b3r4y4b3g1b4r2w4w1g4w2y3y4");
+    let (_, record, _) = record_game(rules, deck, rules.get_basic_player()).unwrap();
+    Viewer::new(&record).unwrap()
+}
+
+#[test]
+fn navigation_is_clamped() {
+    colored::control::set_override(false);
+    let mut viewer = failed_2();
+    let last = viewer.number_of_turns();
+
+    assert_eq!(1, viewer.turn());
+    viewer.back();
+    assert_eq!(1, viewer.turn());
+
+    viewer.forward();
+    viewer.forward();
+    assert_eq!(3, viewer.turn());
+    assert_eq!(2, viewer.state().active_seat());
+
+    viewer.jump_to(last + 10);
+    assert_eq!(last, viewer.turn());
+    viewer.forward();
+    assert_eq!(last, viewer.turn());
+    assert!(viewer.render().contains("Lost."));
+
+    viewer.jump_to(0);
+    assert_eq!(1, viewer.turn());
+}
+
+#[test]
+fn point_of_view_hides_own_cards() {
+    colored::control::set_override(false);
+    let mut viewer = failed_2();
+    viewer.jump_to(20);
+
+    assert_eq!(0, viewer.render().matches("??").count());
+
+    viewer.set_point_of_view(Some(1));
+    let rendered = viewer.render();
+    let bob = rendered
+        .lines()
+        .find(|l| l.get(2..).is_some_and(|l| l.starts_with("Bob ")))
+        .unwrap();
+    assert_eq!(viewer.state().hand(1).len(), bob.matches("??").count());
+    assert_eq!(viewer.state().hand(1).len(), rendered.matches("??").count());
+
+    for _ in 0..3 {
+        viewer.cycle_point_of_view();
+    }
+    assert_eq!(None, viewer.point_of_view());
+}
+
+#[test]
+fn clues_are_shown_on_cards() {
+    colored::control::set_override(false);
+    let mut viewer = failed_2();
+    viewer.jump_to(viewer.number_of_turns());
+
+    let state = viewer.state();
+    let clued = (0..state.number_of_players()).any(|seat| {
+        let hand = state.hand(seat);
+        (1..=hand.len()).any(|position| !hand.hints(position).unwrap().0.is_empty())
+    });
+    assert!(clued);
+
+    let hand = state.hand(0);
+    assert_eq!(None, hand.hints(0));
+    assert_eq!(None, hand.hints(hand.len() + 1));
+    assert!(viewer.render().contains('+'));
+}
+
+#[test]
+fn hidden_plays_stay_hidden_from_the_player() {
+    colored::control::set_override(false);
+    let mut rules = Rules::with_number_of_players(3);
+    rules.hidden_plays = true;
+    let (_, record, _) = record_game(
+        rules,
+        deck_for_game(&rules, 20, 0),
+        rules.get_basic_player(),
+    )
+    .unwrap();
+    let mut viewer = Viewer::new(&record).unwrap();
+
+    let plays: Vec<_> = record
+        .turns()
+        .filter(|turn| matches!(turn.action, Action::Play { .. }))
+        .map(|turn| (turn.turn, turn.seat, turn.revealed.unwrap()))
+        .collect();
+    assert!(!plays.is_empty());
+
+    let mut firework_was_hidden = false;
+    for (turn, seat, card) in plays {
+        viewer.jump_to(turn);
+        viewer.set_point_of_view(Some(seat));
+        let rendered = viewer.render();
+        assert!(rendered.contains("(??)"));
+        assert!(!rendered.contains(&format!("({card})")));
+        viewer.set_point_of_view(Some((seat + 1) % rules.number_of_players));
+        assert!(viewer.render().contains(&format!("({card})")));
+
+        viewer.jump_to(turn + 1);
+        let firework = viewer.state().firework().to_string();
+        viewer.set_point_of_view(None);
+        assert!(viewer.render().contains(&firework));
+        viewer.set_point_of_view(Some(seat));
+        firework_was_hidden |= !viewer.render().contains(&firework);
+    }
+    assert!(firework_was_hidden);
+}